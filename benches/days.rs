@@ -0,0 +1,50 @@
+#[macro_use] extern crate criterion;
+extern crate aoc2018;
+
+use criterion::{black_box, Criterion};
+
+use aoc2018::Solution;
+use aoc2018::days::*;
+use aoc2018::days::day5;
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("day{}", S::DAY));
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(S::INPUT))));
+
+    let input = S::parse(S::INPUT).expect("bundled input parses");
+    group.bench_function("part_one", |b| b.iter(|| S::part_one(black_box(&input))));
+    group.bench_function("part_two", |b| b.iter(|| S::part_two(black_box(&input))));
+
+    group.finish();
+}
+
+fn day5_react(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day5_react");
+    let polymer = Day5::parse(Day5::INPUT).expect("bundled input parses");
+
+    group.bench_function("naive", |b| b.iter(|| day5::react_naive(black_box(polymer.clone()))));
+    group.bench_function("stack", |b| b.iter(|| day5::react(black_box(&polymer))));
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<Day1>(c);
+    bench_day::<Day2>(c);
+    bench_day::<Day3>(c);
+    bench_day::<Day4>(c);
+    bench_day::<Day5>(c);
+    bench_day::<Day6>(c);
+    bench_day::<Day7>(c);
+    bench_day::<Day8>(c);
+    bench_day::<Day9>(c);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = days, day5_react
+}
+
+criterion_main!(benches);
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use toml::Value;
+
+use registry::Part;
+
+/// Expected answers keyed by day and part, read from a TOML file like
+///
+/// ```toml
+/// [1]
+/// 1 = 531
+/// 2 = 76787
+/// ```
+pub struct Answers(HashMap<(u32, Part), String>);
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail{ expected: String },
+    Missing
+}
+
+fn parse_key<T, F>(key: &str, f: F) -> Result<T, String> where F: Fn(&str) -> Option<T> {
+    f(key).ok_or_else(|| format!("unexpected key `{}`", key))
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let root = text.parse::<Value>().map_err(|e| e.to_string())?;
+        let mut answers = HashMap::new();
+
+        for (day_key, parts) in root.as_table().into_iter().flatten() {
+            let day = parse_key(day_key, |k| k.parse::<u32>().ok())?;
+            let parts = parts.as_table().ok_or_else(|| format!("day `{}` should be a table", day_key))?;
+
+            for (part_key, value) in parts {
+                let part = parse_key(part_key, Part::from_string)?;
+                let answer = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Integer(i) => i.to_string(),
+                    _ => return Err(format!("answer for day {} part {} should be a string or an integer", day, part))
+                };
+
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Answers(answers))
+    }
+
+    pub fn check(&self, day: u32, part: Part, actual: &str) -> Verdict {
+        match self.0.get(&(day, part)) {
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail{ expected: expected.clone() },
+            None => Verdict::Missing
+        }
+    }
+}
+
+/// Line by line comparison, `-` marking expected lines and `+` actual ones.
+pub fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut ret = Vec::new();
+
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => ret.push(format!("  {}", e)),
+            (e, a) => {
+                ret.extend(e.map(|e| format!("- {}", e)));
+                ret.extend(a.map(|a| format!("+ {}", a)));
+            }
+        }
+    }
+
+    ret
+}
+
+#[test]
+fn verdicts() {
+    let answers = Answers::parse("[1]\n1 = 531\n2 = \"abc\"\n").expect("should parse");
+
+    assert_eq!(answers.check(1, Part::One, "531"), Verdict::Pass);
+    assert_eq!(answers.check(1, Part::Two, "abd"), Verdict::Fail{ expected: "abc".to_owned() });
+    assert_eq!(answers.check(2, Part::One, "0"), Verdict::Missing);
+    assert_eq!(diff("a\nb", "a\nc"), vec!["  a", "- b", "+ c"]);
+}
@@ -1,47 +1,43 @@
-const INPUT: &'static str = include_str!("inputs/5.txt");
+extern crate aoc2018;
 
-use std::collections::HashSet;
-use std::iter::FromIterator;
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process;
 
-type Seq = Vec<char>;
+use aoc2018::days::day5;
 
-fn annihilate(w: &[char]) -> bool {
-    match w {
-        &[c1, c2] =>
-            c1.to_ascii_lowercase() == c2.to_ascii_lowercase() &&
-            c1.is_lowercase() != c2.is_lowercase(),
+const USAGE: &str = "usage: 5 [<path|->] | 5 --stream <path|->";
 
-        _ => false
-    }
-}
-
-// O(n^2) - Rust is fast ;)
-fn react(mut seq: Seq) -> Seq {
-    while let Some((i, _)) = seq.windows(2).enumerate().filter(|(_, w)| annihilate(w)).nth(0) {
-        seq.drain(i..(i+2));
-    }
-
-    seq
-}
+fn stream(path: &str) -> io::Result<usize> {
+    let reader: Box<dyn Read> = if path == "-" {
+        Box::new(io::stdin())
+    } else {
+        Box::new(File::open(path)?)
+    };
 
-fn part_one(seq: Seq) -> Seq {
-    let r_seq = react(seq);
-    println!("{}", r_seq.len());
-    r_seq
-}
-
-fn part_two(seq: Seq) {
-    let letters: HashSet<char> = HashSet::from_iter(seq.iter().map(char::to_ascii_lowercase));
-
-    let ans = letters
-        .iter()
-        .map(|&l| react(seq.iter().filter(|c| c.to_ascii_lowercase() != l).cloned().collect()).len())
-        .min().expect("answer exists");
-
-    println!("{}", ans);
+    day5::react_stream(reader).map(|polymer| polymer.len())
 }
 
 fn main() {
-    let seq: Seq = INPUT.trim().chars().collect();
-    part_two(part_one(seq));
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("--stream") => {
+            let path = args.get(1).unwrap_or_else(|| {
+                eprintln!("missing value for `--stream`\n{}", USAGE);
+                process::exit(2)
+            });
+
+            match stream(path) {
+                Ok(len) => println!("{}", len),
+                Err(e) => {
+                    eprintln!("{}: {}", path, e);
+                    process::exit(1)
+                }
+            }
+        },
+
+        _ => aoc2018::main::<aoc2018::days::Day5>()
+    }
 }
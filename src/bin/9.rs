@@ -1,74 +1,53 @@
-use std::iter::{once, FromIterator};
-use std::collections::VecDeque;
+extern crate aoc2018;
 
-const N_PLAYERS: usize = 463;
-const N_MARBLES: usize = 71787;
+use std::env;
+use std::process;
 
-struct Circle(VecDeque<usize>);
+use aoc2018::days::Day9;
+use aoc2018::days::day9::Game;
 
-impl Circle {
-    fn new(elem: usize) -> Self {
-        Circle(VecDeque::from_iter(once(elem)))
-    }
-
-    fn rotate_cw(&mut self) {
-        let front = self.0.pop_front().expect("circle nonempty");
-        self.0.push_back(front);
-    }
-
-    fn rotate_ccw(&mut self) {
-        let back = self.0.pop_back().expect("circle nonempty");
-        self.0.push_front(back);
-    }
-
-    fn insert_step(&mut self, elem: usize) {
-        self.rotate_cw();
-        self.0.push_back(elem);
-    }
+const USAGE: &str = "usage: 9 [<path|->] | 9 --players <n> --last-marble <points>";
 
-    fn strange_step(&mut self) -> usize {
-        (0..7).for_each(|_| self.rotate_ccw());
-        let evicted = self.0.pop_back().expect("circle nonempty");
-        self.rotate_cw();
-        evicted
-    }
-}
+fn parse_flags(args: &[String]) -> Result<Game, String> {
+    let mut players = None;
+    let mut last_marble = None;
 
-fn highscore(n_players: usize, last_marble: usize) -> usize {
-    let mut scores = vec![0usize; n_players];
-    let mut circle = Circle::new(0);
+    for pair in args.chunks(2) {
+        let (flag, value) = match pair {
+            [flag, value] => (flag, value),
+            _ => return Err(format!("missing value for `{}`", pair[0]))
+        };
 
-    for marble in 1..=last_marble {
-        if marble % 23 == 0 {
-            let rem = circle.strange_step();
-            scores[marble % n_players] += marble + rem;
+        let value = value.parse::<usize>().map_err(|_| format!("`{}` is not a number", value))?;
 
-        } else {
-            circle.insert_step(marble);
+        match flag.as_str() {
+            "--players" => players = Some(value),
+            "--last-marble" => last_marble = Some(value),
+            _ => return Err(format!("unknown flag `{}`", flag))
         }
     }
 
-    scores.iter().max().cloned().expect("at least one player")
-}
-
-fn part_one() {
-    println!("{}", highscore(N_PLAYERS, N_MARBLES));
-}
+    if players == Some(0) {
+        return Err("`--players` must be at least 1".to_owned());
+    }
 
-fn part_two() {
-    println!("{}", highscore(N_PLAYERS, N_MARBLES * 100));
+    Ok(Game {
+        players: players.ok_or("missing `--players`")?,
+        last_marble: last_marble.ok_or("missing `--last-marble`")?
+    })
 }
 
 fn main() {
-    part_one();
-    part_two();
-}
+    let args: Vec<String> = env::args().skip(1).collect();
 
-#[test]
-fn examples() {
-    assert_eq!(highscore(10, 1618), 8317);
-    assert_eq!(highscore(13, 7999), 146373);
-    assert_eq!(highscore(17, 1104), 2764);
-    assert_eq!(highscore(21, 6111), 54718);
-    assert_eq!(highscore(30, 5807), 37305);
+    if args.first().is_some_and(|arg| arg.starts_with("--")) {
+        let game = parse_flags(&args).unwrap_or_else(|e| {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2)
+        });
+
+        aoc2018::print_answers::<Day9>(&game);
+    } else {
+        aoc2018::main::<Day9>();
+    }
 }
@@ -0,0 +1,353 @@
+extern crate aoc2018;
+#[macro_use] extern crate serde_json;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::process;
+use std::slice::Iter;
+use std::time::Duration;
+
+use aoc2018::{input, Solution};
+use aoc2018::days::{Day3, Day4, Day5};
+use aoc2018::days::day3::{self, OverlapGraph};
+use aoc2018::days::{day4, day5};
+use aoc2018::answers::{self, Answers, Verdict};
+use aoc2018::registry::{self, Entry, Part, Report};
+
+const USAGE: &str = "usage: aoc run <day|all> [--part 1|2] [--input <path|->] [--format text|json]
+       aoc verify [--answers <path>]
+       aoc fabric <output.ppm> [--input <path|->] [--highlight]
+       aoc timeline [--input <path|->] [--html]
+       aoc trace <output.csv> [--input <path|->]";
+
+const DEFAULT_ANSWERS: &str = "answers.toml";
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json
+}
+
+struct Options {
+    days: Vec<&'static Entry>,
+    parts: Vec<Part>,
+    input: Option<String>,
+    format: Format
+}
+
+enum Command {
+    Run(Options),
+    Verify{ answers: String },
+    Fabric{ output: String, input: Option<String>, highlight: bool },
+    Timeline{ input: Option<String>, html: bool },
+    Trace{ output: String, input: Option<String> }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+
+    match args.next().map(String::as_str) {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("verify") => parse_verify(args),
+        Some("fabric") => parse_fabric(args),
+        Some("timeline") => parse_timeline(args),
+        Some("trace") => parse_trace(args),
+        Some(command) => Err(format!("unknown command `{}`", command)),
+        None => Err("missing command".to_owned())
+    }
+}
+
+fn parse_verify(mut args: Iter<String>) -> Result<Command, String> {
+    let mut answers = DEFAULT_ANSWERS.to_owned();
+
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("missing value for `{}`", flag))?;
+
+        match flag.as_str() {
+            "--answers" => answers = value.clone(),
+            _ => return Err(format!("unknown flag `{}`", flag))
+        }
+    }
+
+    Ok(Command::Verify{ answers })
+}
+
+fn parse_fabric(mut args: Iter<String>) -> Result<Command, String> {
+    let output = args.next().ok_or("missing output path")?.clone();
+    let mut input = None;
+    let mut highlight = false;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--input" => input = Some(args.next().ok_or_else(|| format!("missing value for `{}`", flag))?.clone()),
+            "--highlight" => highlight = true,
+            _ => return Err(format!("unknown flag `{}`", flag))
+        }
+    }
+
+    Ok(Command::Fabric{ output, input, highlight })
+}
+
+fn parse_timeline(mut args: Iter<String>) -> Result<Command, String> {
+    let mut input = None;
+    let mut html = false;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--input" => input = Some(args.next().ok_or_else(|| format!("missing value for `{}`", flag))?.clone()),
+            "--html" => html = true,
+            _ => return Err(format!("unknown flag `{}`", flag))
+        }
+    }
+
+    Ok(Command::Timeline{ input, html })
+}
+
+fn parse_trace(mut args: Iter<String>) -> Result<Command, String> {
+    let output = args.next().ok_or("missing output path")?.clone();
+    let mut input = None;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--input" => input = Some(args.next().ok_or_else(|| format!("missing value for `{}`", flag))?.clone()),
+            _ => return Err(format!("unknown flag `{}`", flag))
+        }
+    }
+
+    Ok(Command::Trace{ output, input })
+}
+
+fn parse_run(mut args: Iter<String>) -> Result<Options, String> {
+    let days = match args.next().map(String::as_str) {
+        Some("all") => registry::DAYS.iter().collect(),
+        Some(day) => vec![day.parse().ok().and_then(registry::find).ok_or_else(|| format!("unknown day `{}`", day))?],
+        None => return Err("missing day".to_owned())
+    };
+
+    let mut parts = Part::BOTH.to_vec();
+    let mut input = None;
+    let mut format = Format::Text;
+
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("missing value for `{}`", flag))?;
+
+        match flag.as_str() {
+            "--part" => parts = vec![Part::from_string(value).ok_or_else(|| format!("unknown part `{}`", value))?],
+            "--input" => input = Some(value.clone()),
+            "--format" => format = match value.as_str() {
+                "text" => Format::Text,
+                "json" => Format::Json,
+                _ => return Err(format!("unknown format `{}`", value))
+            },
+            _ => return Err(format!("unknown flag `{}`", flag))
+        }
+    }
+
+    if input.is_some() && days.len() > 1 {
+        return Err("`--input` requires a single day".to_owned());
+    }
+
+    Ok(Options{ days, parts, input, format })
+}
+
+fn total_elapsed(report: &Report) -> Duration {
+    report.answers.iter().fold(report.parse_elapsed, |total, a| total + a.elapsed)
+}
+
+fn print_json_report(report: &Report) {
+    for answer in report.answers.iter() {
+        println!("{}", json!({
+            "day": report.day,
+            "part": answer.part.number(),
+            "answer": answer.value,
+            "elapsed_ns": answer.elapsed.as_nanos() as u64
+        }));
+    }
+
+    if let Some(ref diagnostics) = report.diagnostics {
+        println!("{}", json!({ "day": report.day, "diagnostics": diagnostics }));
+    }
+}
+
+fn print_report(report: &Report) {
+    for answer in report.answers.iter() {
+        println!("day {} part {}: {} ({:?})", report.day, answer.part, answer.answer, answer.elapsed);
+    }
+
+    println!("day {} total: {:?} (parse {:?})", report.day, total_elapsed(report), report.parse_elapsed);
+}
+
+fn run(options: &Options) -> bool {
+    let mut total = Duration::from_secs(0);
+    let mut ok = true;
+
+    for entry in options.days.iter() {
+        let input = read_input(options.input.as_deref(), entry.input);
+
+        match (entry.solve)(&input, &options.parts, options.format == Format::Json) {
+            Ok(report) => {
+                match options.format {
+                    Format::Text => print_report(&report),
+                    Format::Json => print_json_report(&report)
+                }
+
+                total += total_elapsed(&report);
+            },
+
+            Err(e) => {
+                eprintln!("error: {}", e);
+                ok = false;
+            }
+        }
+    }
+
+    if options.days.len() > 1 && options.format == Format::Text {
+        println!("total: {:?}", total);
+    }
+
+    ok
+}
+
+fn verify(answers_path: &str) -> bool {
+    let answers = fs::read_to_string(answers_path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| Answers::parse(&text))
+        .unwrap_or_else(|e| {
+            eprintln!("{}: {}", answers_path, e);
+            process::exit(1)
+        });
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for entry in registry::DAYS.iter() {
+        let report = match (entry.solve)(entry.input, &Part::BOTH, false) {
+            Ok(report) => report,
+            Err(e) => {
+                println!("day {}: FAIL\n  error: {}", entry.day, e);
+                failed += Part::BOTH.len();
+                continue;
+            }
+        };
+
+        for answer in report.answers.iter() {
+            match answers.check(report.day, answer.part, &answer.answer) {
+                Verdict::Pass => {
+                    println!("day {} part {}: pass", report.day, answer.part);
+                    passed += 1;
+                },
+
+                Verdict::Fail{ expected } => {
+                    println!("day {} part {}: FAIL", report.day, answer.part);
+                    for line in answers::diff(&expected, &answer.answer) {
+                        println!("  {}", line);
+                    }
+
+                    failed += 1;
+                },
+
+                Verdict::Missing => {
+                    println!("day {} part {}: missing (got {})", report.day, answer.part, answer.answer);
+                    missing += 1;
+                }
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
+}
+
+fn read_input(input_path: Option<&str>, bundled: &str) -> String {
+    match input_path {
+        Some(path) => input::read(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            process::exit(1)
+        }),
+
+        None => bundled.to_owned()
+    }
+}
+
+fn fabric(output: &str, input_path: Option<&str>, highlight: bool) -> bool {
+    let input = read_input(input_path, Day3::INPUT);
+
+    let rects = match Day3::parse(&input) {
+        Ok(rects) => rects,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false;
+        }
+    };
+
+    let isolated = if highlight { OverlapGraph::new(&rects).isolated() } else { Vec::new() };
+    let highlighted: Vec<_> = rects.iter().filter(|r| isolated.contains(&r.id)).collect();
+
+    let written = File::create(output)
+        .and_then(|file| day3::write_ppm(&mut BufWriter::new(file), &day3::fabric(&rects), &highlighted));
+
+    if let Err(e) = written {
+        eprintln!("{}: {}", output, e);
+        return false;
+    }
+
+    true
+}
+
+fn timeline(input_path: Option<&str>, html: bool) -> bool {
+    let input = read_input(input_path, Day4::INPUT);
+
+    match day4::log(&input) {
+        Ok(log) => {
+            print!("{}", if html { day4::timeline_html(&log) } else { day4::timeline(&log) });
+            true
+        },
+
+        Err(e) => {
+            eprintln!("error: {}", e);
+            false
+        }
+    }
+}
+
+fn trace(output: &str, input_path: Option<&str>) -> bool {
+    let input = read_input(input_path, Day5::INPUT);
+
+    let polymer = match Day5::parse(&input) {
+        Ok(polymer) => polymer,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false;
+        }
+    };
+
+    let written = File::create(output)
+        .and_then(|file| day5::trace(&polymer).write_csv(&mut BufWriter::new(file)));
+
+    if let Err(e) = written {
+        eprintln!("{}: {}", output, e);
+        return false;
+    }
+
+    true
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = parse_args(&args).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        process::exit(2)
+    });
+
+    let ok = match command {
+        Command::Run(ref options) => run(options),
+        Command::Verify{ ref answers } => verify(answers),
+        Command::Fabric{ ref output, ref input, highlight } => fabric(output, input.as_ref().map(String::as_str), highlight),
+        Command::Timeline{ ref input, html } => timeline(input.as_ref().map(String::as_str), html),
+        Command::Trace{ ref output, ref input } => trace(output, input.as_ref().map(String::as_str))
+    };
+
+    if !ok {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::iter::once;
+use std::num::IntErrorKind;
+
+use serde_json::Value;
+
+use error::{ErrorKind, ParseError, Unexpected};
+use Solution;
+
+pub struct Day1;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Repeat {
+    /// `frequency` is reached twice after `changes` changes in total, the last one being
+    /// change number `position` (zero based) during pass number `cycle` over the list.
+    Found{ frequency: i64, changes: usize, cycle: usize, position: usize },
+    Never
+}
+
+impl Repeat {
+    fn after(frequency: i64, changes: usize, n: usize) -> Repeat {
+        Repeat::Found{ frequency, changes, cycle: (changes - 1) / n, position: (changes - 1) % n }
+    }
+}
+
+impl From<Repeat> for Value {
+    fn from(repeat: Repeat) -> Value {
+        match repeat {
+            Repeat::Found{ frequency, .. } => frequency.into(),
+            Repeat::Never => Value::Null
+        }
+    }
+}
+
+impl fmt::Display for Repeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Repeat::Found{ frequency, .. } => write!(f, "{}", frequency),
+            Repeat::Never => write!(f, "no repeat")
+        }
+    }
+}
+
+fn parse_change(token: &str) -> Result<i32, Unexpected<'_>> {
+    token.parse::<i32>().map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Unexpected::new(token, ErrorKind::OutOfRange("i32")),
+        _ => Unexpected::number(token, e)
+    })
+}
+
+/// Every frequency reached while cycling through the changes, starting with 0.
+pub fn trajectory(numbers: &[i32]) -> impl Iterator<Item=i64> + '_ {
+    once(0).chain(numbers.iter().cycle().scan(0i64, |s, &n| { *s += i64::from(n); Some(*s) }))
+}
+
+/// Frequencies at the start of each change during the first pass, and the drift per pass.
+fn first_pass(numbers: &[i32]) -> (Vec<i64>, i64) {
+    let mut freqs = Vec::with_capacity(numbers.len());
+    let drift = numbers.iter().fold(0i64, |f, &n| { freqs.push(f); f + i64::from(n) });
+    (freqs, drift)
+}
+
+/// Frequency reached after `i` changes during pass `k` is `freqs[i] + k * drift`, so
+/// a later pass can only revisit frequencies congruent modulo the drift, and the
+/// earliest revisit of each is by the nearest one in the direction of the drift.
+pub fn first_repeat(numbers: &[i32]) -> Repeat {
+    let n = numbers.len();
+    let (freqs, drift) = first_pass(numbers);
+
+    let mut seen = HashSet::new();
+    if let Some(i) = freqs.iter().position(|&f| !seen.insert(f)) {
+        return Repeat::after(freqs[i], i, n);
+    }
+
+    if n == 0 {
+        return Repeat::Never;
+    }
+
+    if drift == 0 {
+        return Repeat::after(freqs[0], n, n);
+    }
+
+    let mut residues: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (i, &f) in freqs.iter().enumerate() {
+        residues.entry(f.rem_euclid(drift)).or_default().push((f * drift.signum(), i));
+    }
+
+    residues.values_mut()
+        .flat_map(|group| {
+            group.sort_unstable();
+            group.windows(2).map(|w| {
+                let ((from, i), (to, _)) = (w[0], w[1]);
+                let passes = ((to - from) / drift.abs()) as usize;
+                (passes * n + i, to * drift.signum())
+            }).collect::<Vec<_>>()
+        })
+        .min()
+        .map_or(Repeat::Never, |(changes, frequency)| Repeat::after(frequency, changes, n))
+}
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type PartOne = i64;
+    type PartTwo = Repeat;
+
+    const DAY: u32 = 1;
+    const INPUT: &'static str = include_str!("inputs/1.txt");
+
+    /// Changes are separated by newlines, commas or any other whitespace, as in `+1, -2, +3, +1`.
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        input.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| parse_change(token).map_err(|u| ParseError::locate(Self::DAY, input, u)))
+            .collect()
+    }
+
+    fn part_one(numbers: &Vec<i32>) -> i64 {
+        numbers.iter().map(|&n| i64::from(n)).sum()
+    }
+
+    fn part_two(numbers: &Vec<i32>) -> Repeat {
+        first_repeat(numbers)
+    }
+
+    fn diagnostics(numbers: &Vec<i32>) -> Option<Value> {
+        let (_, drift) = first_pass(numbers);
+        let first_repeat = match first_repeat(numbers) {
+            Repeat::Found{ frequency, changes, cycle, position } =>
+                json!({ "frequency": frequency, "changes": changes, "cycle": cycle, "position": position }),
+            Repeat::Never => Value::Null
+        };
+
+        Some(json!({ "drift": drift, "first_repeat": first_repeat }))
+    }
+}
+
+#[test]
+fn examples() {
+    let parse = |s| Day1::parse(s).expect("example parses");
+    let frequency = |s| match Day1::part_two(&parse(s)) {
+        Repeat::Found{ frequency, .. } => Some(frequency),
+        Repeat::Never => None
+    };
+
+    assert_eq!(Day1::part_one(&parse("+1, -2, +3, +1")), 3);
+    assert_eq!(Day1::part_one(&parse("+1, +1, +1")), 3);
+    assert_eq!(Day1::part_one(&parse("+1, +1, -2")), 0);
+    assert_eq!(Day1::part_one(&parse("-1, -2, -3")), -6);
+
+    assert_eq!(frequency("+1, -2, +3, +1"), Some(2));
+    assert_eq!(frequency("+1, -1"), Some(0));
+    assert_eq!(frequency("+3, +3, +4, -2, -4"), Some(10));
+    assert_eq!(frequency("-6, +3, +8, +5, -6"), Some(5));
+    assert_eq!(frequency("+7, +7, -2, -7, -4"), Some(14));
+
+    assert_eq!(Day1::part_two(&parse("+1 +1")), Repeat::Never);
+    assert_eq!(Day1::part_two(&parse("+1\n-2\n+3\n+1")), Repeat::Found{ frequency: 2, changes: 6, cycle: 1, position: 1 });
+
+    let e = Day1::parse("+1, -2\n+3, +2147483648").expect_err("out of range");
+    assert_eq!((e.line, e.column, e.kind), (2, 5, ErrorKind::OutOfRange("i32")));
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn simulate(numbers: &[i32], limit: usize) -> Option<(i64, usize)> {
+        let mut seen = HashSet::new();
+        trajectory(numbers).take(limit).enumerate().find(|&(_, f)| !seen.insert(f)).map(|(t, f)| (f, t))
+    }
+
+    proptest! {
+        #[test]
+        fn first_repeat_matches_simulation(numbers in prop::collection::vec(-20..20i32, 1..12)) {
+            let limit = 10_000;
+            match (first_repeat(&numbers), simulate(&numbers, limit)) {
+                (Repeat::Found{ frequency, changes, .. }, Some(expected)) => prop_assert_eq!((frequency, changes), expected),
+                (Repeat::Found{ changes, .. }, None) => prop_assert!(changes >= limit),
+                (Repeat::Never, found) => prop_assert_eq!(found, None)
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use error::{parse_lines, ParseError, Unexpected};
+use Solution;
+
+pub struct Day2;
+
+pub type LetterCounts = HashMap<char, usize>;
+
+pub fn letter_counts(s: &str) -> LetterCounts {
+    let mut counts = HashMap::with_capacity(26);
+    for c in s.chars() {
+        let count = counts.entry(c).or_default();
+        *count += 1;
+    }
+
+    counts
+}
+
+/// For every `k`, the number of words containing some letter exactly `k` times.
+pub fn repeat_histogram<S: AsRef<str>>(words: &[S]) -> BTreeMap<usize, usize> {
+    let mut histogram = BTreeMap::new();
+
+    for w in words {
+        let repeats: HashSet<usize> = letter_counts(w.as_ref()).values().cloned().collect();
+        for k in repeats {
+            *histogram.entry(k).or_default() += 1;
+        }
+    }
+
+    histogram
+}
+
+/// Product of the histogram entries for each of `ks`; the puzzle uses `&[2, 3]`.
+pub fn checksum<S: AsRef<str>>(words: &[S], ks: &[usize]) -> usize {
+    let histogram = repeat_histogram(words);
+    ks.iter().map(|k| histogram.get(k).cloned().unwrap_or(0)).product()
+}
+
+fn hamming(w1: &[char], w2: &[char]) -> usize {
+    w1.iter().zip(w2.iter()).filter(|(c1, c2)| c1 != c2).count()
+}
+
+fn to_chars<S: AsRef<str>>(words: &[S]) -> Vec<Vec<char>> {
+    words.iter().map(|w| w.as_ref().chars().collect()).collect()
+}
+
+fn bucket_pairs(bucket: &[usize]) -> impl Iterator<Item=(usize, usize)> + '_ {
+    bucket.iter().enumerate().flat_map(move |(pos, &i)| bucket[pos+1..].iter().map(move |&j| (i, j)))
+}
+
+/// Pairs `(i, j)` with `i < j` of words differing in exactly one position: such words
+/// become equal once that position is masked out, so they share a bucket.
+pub fn hamming_one_pairs<S: AsRef<str>>(words: &[S]) -> Vec<(usize, usize)> {
+    let words = to_chars(words);
+    let mut buckets: HashMap<(&[char], &[char]), Vec<usize>> = HashMap::new();
+
+    for (i, w) in words.iter().enumerate() {
+        for p in 0..w.len() {
+            buckets.entry((&w[..p], &w[p+1..])).or_default().push(i);
+        }
+    }
+
+    let mut pairs: Vec<_> = buckets.values()
+        .flat_map(|bucket| bucket_pairs(bucket))
+        .filter(|&(i, j)| words[i] != words[j])
+        .collect();
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Pairs `(i, j)` with `i < j` of equally long words differing in at most `k` positions.
+/// Split into `k + 1` blocks, such words agree on at least one block entirely.
+pub fn pairs_within<S: AsRef<str>>(words: &[S], k: usize) -> Vec<(usize, usize)> {
+    let words = to_chars(words);
+    let mut buckets: HashMap<(usize, usize, &[char]), Vec<usize>> = HashMap::new();
+
+    for (i, w) in words.iter().enumerate() {
+        let blocks = k + 1;
+        for b in 0..blocks {
+            let block = &w[b * w.len() / blocks..(b + 1) * w.len() / blocks];
+            buckets.entry((w.len(), b, block)).or_default().push(i);
+        }
+    }
+
+    let candidates: HashSet<(usize, usize)> = buckets.values()
+        .flat_map(|bucket| bucket_pairs(bucket))
+        .collect();
+
+    let mut pairs: Vec<_> = candidates.into_iter()
+        .filter(|&(i, j)| hamming(&words[i], &words[j]) <= k)
+        .collect();
+
+    pairs.sort_unstable();
+    pairs
+}
+
+fn common_letters(w1: &str, w2: &str) -> String {
+    w1.chars().zip(w2.chars())
+        .filter_map(|(c1, c2)| if c1 == c2 { Some(c1) } else { None })
+        .collect()
+}
+
+impl Solution for Day2 {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = String;
+
+    const DAY: u32 = 2;
+    const INPUT: &'static str = include_str!("inputs/2.txt");
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        let words = parse_lines(Self::DAY, input, Ok)?;
+
+        if let Some(w) = words.iter().find(|w| w.len() != words[0].len()) {
+            let unexpected = Unexpected::expected(w, "box ID as long as the first one");
+            return Err(ParseError::locate(Self::DAY, input, unexpected));
+        }
+
+        Ok(words.into_iter().map(str::to_owned).collect())
+    }
+
+    fn part_one(words: &Vec<String>) -> usize {
+        checksum(words, &[2, 3])
+    }
+
+    fn part_two(words: &Vec<String>) -> String {
+        hamming_one_pairs(words).into_iter()
+            .map(|(i, j)| common_letters(&words[i], &words[j]))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[test]
+fn examples() {
+    let words = Day2::parse("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n").expect("example parses");
+    assert_eq!(Day2::part_one(&words), 12);
+    assert_eq!(repeat_histogram(&words).into_iter().collect::<Vec<_>>(), vec![(1, 6), (2, 4), (3, 3)]);
+    assert_eq!(checksum(&words, &[1, 2, 3]), 72);
+    assert_eq!(checksum(&words, &[4]), 0);
+
+    let words = Day2::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n").expect("example parses");
+    assert_eq!(Day2::part_two(&words), "fgij");
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn brute_force(words: &[String], accept: &dyn Fn(usize) -> bool) -> Vec<(usize, usize)> {
+        let words = to_chars(words);
+        let mut pairs = Vec::new();
+
+        for i in 0..words.len() {
+            for j in i+1..words.len() {
+                if words[i].len() == words[j].len() && accept(hamming(&words[i], &words[j])) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+
+        pairs
+    }
+
+    fn arb_words() -> impl Strategy<Value=Vec<String>> {
+        (1..8usize).prop_flat_map(|len| prop::collection::vec(prop::string::string_regex(&format!("[ab]{{{}}}", len)).expect("valid regex"), 0..30))
+    }
+
+    proptest! {
+        #[test]
+        fn hamming_one_matches_brute_force(words in arb_words()) {
+            prop_assert_eq!(hamming_one_pairs(&words), brute_force(&words, &|d| d == 1));
+        }
+
+        #[test]
+        fn pairs_within_matches_brute_force(words in arb_words(), k in 0..4usize) {
+            prop_assert_eq!(pairs_within(&words, k), brute_force(&words, &|d| d <= k));
+        }
+    }
+}
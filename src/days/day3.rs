@@ -0,0 +1,420 @@
+const N: usize = 1000;
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+
+use ndarray::Array2;
+use regex::Regex;
+use serde_json::Value;
+
+use error::{parse_lines, ErrorKind, ParseError, Unexpected};
+use Solution;
+
+lazy_static! {
+    static ref LINE_RE: Regex = Regex::new(r"^#(\d+) @ (\d+),(\d+): (\d+)x(\d+)$")
+        .expect("constant regex is always correct, qed.");
+}
+
+pub struct Day3;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Rect {
+    pub id: usize,
+    pub x: usize,
+    pub y: usize,
+    pub w: usize,
+    pub h: usize
+}
+
+impl Rect {
+    fn pixels(&self) -> impl Iterator<Item=(usize, usize)> {
+        iproduct!(self.x..(self.x+self.w), self.y..(self.y+self.h))
+    }
+
+    fn within(&self, n: usize) -> bool {
+        self.x + self.w <= n && self.y + self.h <= n
+    }
+
+    fn overlap(&self, other: &Rect) -> usize {
+        let w = (self.x + self.w).min(other.x + other.w).saturating_sub(self.x.max(other.x));
+        let h = (self.y + self.h).min(other.y + other.h).saturating_sub(self.y.max(other.y));
+        w * h
+    }
+}
+
+fn parse_line(line: &str) -> Result<Rect, Unexpected<'_>> {
+    let captures = LINE_RE.captures(line)
+        .ok_or_else(|| Unexpected::expected(line, "claim like `#1 @ 1,3: 4x4`"))?;
+
+    let group = |i| captures.get(i).expect("all groups participate, qed.").as_str();
+
+    let uss = (1..6)
+        .map(|i| group(i).parse::<usize>().map_err(|e| Unexpected::number(group(i), e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let rect = match *uss.as_slice() {
+        [id, x, y, w, h] => Rect{ id, x, y, w, h },
+        _ => unreachable!()
+    };
+
+    // Far edges are computed everywhere, so they have to fit as well.
+    if rect.x.checked_add(rect.w).is_none() {
+        return Err(Unexpected::new(group(4), ErrorKind::OutOfRange("usize")));
+    }
+
+    if rect.y.checked_add(rect.h).is_none() {
+        return Err(Unexpected::new(group(5), ErrorKind::OutOfRange("usize")));
+    }
+
+    Ok(rect)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfFabric {
+    pub id: usize
+}
+
+impl fmt::Display for OutOfFabric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "claim #{} does not fit into {}x{} fabric", self.id, N, N)
+    }
+}
+
+/// Area claimed at least twice, counted on a fixed `N`x`N` fabric.
+pub fn overlap_area_grid(rects: &[Rect]) -> Result<usize, OutOfFabric> {
+    if let Some(r) = rects.iter().find(|r| !r.within(N)) {
+        return Err(OutOfFabric{ id: r.id });
+    }
+
+    let mut grid = vec![[0usize; N]; N];
+
+    rects.iter().flat_map(|r| r.pixels()).for_each(|(i, j)| {
+        grid[i][j] += 1;
+    });
+
+    Ok(grid.iter().flat_map(|s| s.iter()).filter(|&&c| c > 1usize).count())
+}
+
+/// Area claimed at least twice, sweeping a line along x over the compressed y coordinates
+/// so that the cost depends on the number of claims rather than on the fabric size.
+pub fn overlap_area(rects: &[Rect]) -> usize {
+    let mut ys: Vec<usize> = rects.iter().flat_map(|r| vec![r.y, r.y + r.h]).collect();
+    ys.sort_unstable();
+    ys.dedup();
+
+    let mut events: Vec<(usize, i32, &Rect)> = rects.iter()
+        .flat_map(|r| vec![(r.x, 1, r), (r.x + r.w, -1, r)])
+        .collect();
+    events.sort_unstable_by_key(|&(x, delta, _)| (x, delta));
+
+    let mut cover = vec![0i32; ys.len().saturating_sub(1)];
+    let mut prev_x = 0;
+    let mut area = 0;
+
+    for (x, delta, r) in events {
+        let overlapped: usize = cover.iter().enumerate()
+            .filter(|&(_, &c)| c > 1)
+            .map(|(k, _)| ys[k+1] - ys[k])
+            .sum();
+
+        area += (x - prev_x) * overlapped;
+        prev_x = x;
+
+        let from = ys.binary_search(&r.y).expect("all y are compressed, qed.");
+        let to = ys.binary_search(&(r.y + r.h)).expect("all y are compressed, qed.");
+        for c in cover[from..to].iter_mut() {
+            *c += delta;
+        }
+    }
+
+    area
+}
+
+pub struct OverlapGraph {
+    /// Every claim id along with the claims it overlaps and the overlapped areas.
+    pub neighbours: BTreeMap<usize, Vec<(usize, usize)>>
+}
+
+impl OverlapGraph {
+    pub fn new(rects: &[Rect]) -> OverlapGraph {
+        let mut neighbours: BTreeMap<usize, Vec<(usize, usize)>> = rects.iter().map(|r| (r.id, Vec::new())).collect();
+
+        let mut by_x: Vec<&Rect> = rects.iter().collect();
+        by_x.sort_unstable_by_key(|r| r.x);
+
+        for (i, r1) in by_x.iter().enumerate() {
+            for r2 in by_x[i+1..].iter().take_while(|r2| r2.x < r1.x + r1.w) {
+                let area = r1.overlap(r2);
+                if area > 0 {
+                    neighbours.get_mut(&r1.id).expect("all ids are present, qed.").push((r2.id, area));
+                    neighbours.get_mut(&r2.id).expect("all ids are present, qed.").push((r1.id, area));
+                }
+            }
+        }
+
+        for adj in neighbours.values_mut() {
+            adj.sort_unstable();
+        }
+
+        OverlapGraph{ neighbours }
+    }
+
+    /// Groups of claims connected through overlaps, each sorted by id.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+
+        for &id in self.neighbours.keys() {
+            if !seen.insert(id) {
+                continue;
+            }
+
+            let mut component = vec![id];
+            let mut stack = vec![id];
+
+            while let Some(cur) = stack.pop() {
+                for &(adj, _) in self.neighbours[&cur].iter() {
+                    if seen.insert(adj) {
+                        component.push(adj);
+                        stack.push(adj);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Claims overlapping no other claim.
+    pub fn isolated(&self) -> Vec<usize> {
+        self.neighbours.iter().filter(|(_, adj)| adj.is_empty()).map(|(&id, _)| id).collect()
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy)]
+pub enum CellRect {
+    Free,
+    Single,
+    Multiple
+}
+
+impl CellRect {
+    fn fill(&self) -> CellRect {
+        match self {
+            CellRect::Free => CellRect::Single,
+            CellRect::Single => CellRect::Multiple,
+            CellRect::Multiple => CellRect::Multiple
+        }
+    }
+}
+
+/// Claim state of every square inch, the fabric extending as far as the claims do.
+pub fn fabric(rects: &[Rect]) -> Array2<CellRect> {
+    let width = rects.iter().map(|r| r.x + r.w).max().unwrap_or(0);
+    let height = rects.iter().map(|r| r.y + r.h).max().unwrap_or(0);
+    let mut grid = Array2::from_elem((width, height), CellRect::Free);
+
+    for r in rects {
+        for ix in r.pixels() {
+            grid[ix] = grid[ix].fill();
+        }
+    }
+
+    grid
+}
+
+impl CellRect {
+    fn color(&self) -> [u8; 3] {
+        match self {
+            CellRect::Free => [32, 32, 32],
+            CellRect::Single => [70, 130, 180],
+            CellRect::Multiple => [220, 50, 47]
+        }
+    }
+}
+
+const HIGHLIGHT_COLOR: [u8; 3] = [133, 200, 0];
+
+/// Writes the fabric as a binary PPM image, one pixel per square inch, with the
+/// `highlighted` claims drawn over it.
+pub fn write_ppm<W: Write>(out: &mut W, grid: &Array2<CellRect>, highlighted: &[&Rect]) -> io::Result<()> {
+    let (width, height) = grid.dim();
+    let mut pixels: Vec<u8> = Vec::with_capacity(width * height * 3);
+
+    for y in 0..height {
+        for x in 0..width {
+            let highlight = highlighted.iter()
+                .any(|r| r.x <= x && x < r.x + r.w && r.y <= y && y < r.y + r.h);
+
+            pixels.extend_from_slice(&if highlight { HIGHLIGHT_COLOR } else { grid[(x, y)].color() });
+        }
+    }
+
+    write!(out, "P6\n{} {}\n255\n", width, height)?;
+    out.write_all(&pixels)
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Intact {
+    Claim(usize),
+    Nowhere
+}
+
+impl From<Intact> for Value {
+    fn from(intact: Intact) -> Value {
+        match intact {
+            Intact::Claim(id) => id.into(),
+            Intact::Nowhere => Value::Null
+        }
+    }
+}
+
+impl fmt::Display for Intact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Intact::Claim(id) => write!(f, "{}", id),
+            Intact::Nowhere => write!(f, "no intact claim")
+        }
+    }
+}
+
+impl Solution for Day3 {
+    type Input = Vec<Rect>;
+    type PartOne = usize;
+    type PartTwo = Intact;
+
+    const DAY: u32 = 3;
+    const INPUT: &'static str = include_str!("inputs/3.txt");
+
+    fn parse(input: &str) -> Result<Vec<Rect>, ParseError> {
+        parse_lines(Self::DAY, input, parse_line)
+    }
+
+    fn part_one(rects: &Vec<Rect>) -> usize {
+        overlap_area(rects)
+    }
+
+    fn part_two(rects: &Vec<Rect>) -> Intact {
+        OverlapGraph::new(rects).isolated().first().map_or(Intact::Nowhere, |&id| Intact::Claim(id))
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+
+#[test]
+fn examples() {
+    let rects = Day3::parse(EXAMPLE).expect("example parses");
+
+    assert_eq!(Day3::part_one(&rects), 4);
+    assert_eq!(Day3::part_two(&rects), Intact::Claim(3));
+    assert_eq!(overlap_area_grid(&rects), Ok(4));
+}
+
+#[test]
+fn overlap_graph() {
+    let rects = Day3::parse(EXAMPLE).expect("example parses");
+
+    let graph = OverlapGraph::new(&rects);
+    assert_eq!(graph.neighbours[&1], vec![(2, 4)]);
+    assert_eq!(graph.components(), vec![vec![1, 2], vec![3]]);
+    assert_eq!(graph.isolated(), vec![3]);
+    assert_eq!(fabric(&rects).iter().filter(|&&c| c == CellRect::Multiple).count(), 4);
+}
+
+#[test]
+fn ppm_image() {
+    let rects = Day3::parse(EXAMPLE).expect("example parses");
+
+    let mut ppm = Vec::new();
+    write_ppm(&mut ppm, &fabric(&rects), &[&rects[2]]).expect("writes to memory");
+    assert!(ppm.starts_with(b"P6\n7 7\n255\n"));
+    assert_eq!(ppm.len(), 11 + 7 * 7 * 3);
+    assert_eq!(&ppm[11 + (5 * 7 + 5) * 3..][..3], &HIGHLIGHT_COLOR);
+}
+
+#[test]
+fn far_edge_overflow() {
+    let e = Day3::parse("#1 @ 18446744073709551615,0: 2x2").expect_err("far edge overflows");
+    assert_eq!((e.column, e.kind), (30, ErrorKind::OutOfRange("usize")));
+
+    let e = Day3::parse("#1 @ 0,18446744073709551615: 2x2").expect_err("far edge overflows");
+    assert_eq!((e.column, e.kind), (32, ErrorKind::OutOfRange("usize")));
+}
+
+#[test]
+fn out_of_fabric() {
+    let rects = Day3::parse("#1 @ 990,3: 4x4\n#2 @ 998,1: 4x4\n#3 @ 1000,3: 2x2\n").expect("claims parse");
+    assert_eq!(overlap_area_grid(&rects), Err(OutOfFabric{ id: 2 }));
+    assert_eq!(Day3::part_one(&rects), 4);
+}
+
+#[test]
+fn no_intact_claim() {
+    let rects = Day3::parse("#1 @ 0,0: 2x2\n#2 @ 1,1: 2x2\n").expect("claims parse");
+    assert_eq!(Day3::part_two(&rects), Intact::Nowhere);
+}
+
+#[cfg(test)]
+mod properties {
+    use std::collections::HashMap;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn arb_rect() -> impl Strategy<Value=Rect> {
+        (1..2000usize, 0..60usize, 0..60usize, 1..20usize, 1..20usize)
+            .prop_map(|(id, x, y, w, h)| Rect{ id, x, y, w, h })
+    }
+
+    fn format_rect(r: &Rect) -> String {
+        format!("#{} @ {},{}: {}x{}", r.id, r.x, r.y, r.w, r.h)
+    }
+
+    proptest! {
+        #[test]
+        fn claims_round_trip(rects in prop::collection::vec(arb_rect(), 0..20)) {
+            let input = rects.iter().map(format_rect).collect::<Vec<_>>().join("\n");
+            prop_assert_eq!(Day3::parse(&input).expect("formatted claims parse"), rects);
+        }
+
+        #[test]
+        fn overlap_matches_pixel_count(rects in prop::collection::vec(arb_rect(), 1..20)) {
+            let mut covered = HashMap::new();
+            for pixel in rects.iter().flat_map(|r| r.pixels()) {
+                *covered.entry(pixel).or_insert(0) += 1;
+            }
+
+            let expected = covered.values().filter(|&&c| c > 1).count();
+            prop_assert_eq!(Day3::part_one(&rects), expected);
+            prop_assert_eq!(overlap_area_grid(&rects), Ok(expected));
+        }
+
+        #[test]
+        fn overlap_graph_matches_pixels(rects in prop::collection::vec(arb_rect(), 1..20)) {
+            let rects: Vec<Rect> = rects.into_iter().enumerate().map(|(id, r)| Rect{ id, ..r }).collect();
+            let graph = OverlapGraph::new(&rects);
+
+            for r1 in rects.iter() {
+                let pixels: HashSet<_> = r1.pixels().collect();
+                let expected: Vec<(usize, usize)> = rects.iter()
+                    .filter(|r2| r2.id != r1.id)
+                    .map(|r2| (r2.id, r2.pixels().filter(|p| pixels.contains(p)).count()))
+                    .filter(|&(_, area)| area > 0)
+                    .collect();
+
+                prop_assert_eq!(&graph.neighbours[&r1.id], &expected);
+            }
+
+            let grid = fabric(&rects);
+            for id in graph.isolated() {
+                prop_assert!(rects[id].pixels().all(|ix| grid[ix] == CellRect::Single));
+            }
+        }
+    }
+}
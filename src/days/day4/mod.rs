@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde_json::Value;
+
+use error::{end_of, parse_lines, ErrorKind, ParseError, Unexpected};
+use Solution;
+
+const RE_PREFIX: &str = r"^\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}\]";
+const RE_GUARD: &str = r"^ Guard #(?P<id>\d+) begins shift$";
+const RE_SLEEP: &str = r"^ falls asleep$";
+const RE_AWAKE: &str = r"^ wakes up$";
+
+lazy_static! {
+    static ref PREFIX_RE: Regex = Regex::new(RE_PREFIX).expect("regex should compile");
+    static ref GUARD_RE: Regex = Regex::new(RE_GUARD).expect("regex should compile");
+    static ref SLEEP_RE: Regex = Regex::new(RE_SLEEP).expect("regex should compile");
+    static ref AWAKE_RE: Regex = Regex::new(RE_AWAKE).expect("regex should compile");
+}
+
+pub mod stats;
+
+use self::stats::Stats;
+
+pub struct Day4;
+
+pub type GuardId = usize;
+
+enum Action {
+    BeginShift(GuardId),
+    Sleep,
+    Awake
+}
+
+struct Event<'a> {
+    datetime: NaiveDateTime,
+    action: Action,
+    line: &'a str
+}
+
+fn parse_line(line: &str) -> Result<Event<'_>, Unexpected<'_>> {
+    let prefix_match = PREFIX_RE.find(line)
+        .ok_or_else(|| Unexpected::expected(line, "timestamp like `[1518-11-01 00:00]`"))?;
+
+    let tail = &line[prefix_match.end()..];
+
+    let action = if SLEEP_RE.is_match(tail) {
+        Action::Sleep
+    } else if AWAKE_RE.is_match(tail) {
+        Action::Awake
+    } else if let Some(id) = GUARD_RE.captures(tail).and_then(|c| c.name("id")) {
+        Action::BeginShift(id.as_str().parse::<usize>().map_err(|e| Unexpected::number(id.as_str(), e))?)
+    } else {
+        return Err(Unexpected::expected(tail.trim_start(), "`Guard #<id> begins shift`, `falls asleep` or `wakes up`"));
+    };
+
+    let datetime = NaiveDateTime::parse_from_str(prefix_match.as_str(), "[%Y-%m-%d %H:%M]")
+        .map_err(|_| Unexpected::expected(prefix_match.as_str(), "valid date and time"))?;
+
+    Ok(Event{ datetime, action, line })
+}
+
+/// Events in chronological order, simultaneous ones keeping their order in the log.
+fn sorted_events(input: &str) -> Result<Vec<Event<'_>>, ParseError> {
+    let mut vec = parse_lines(Day4::DAY, input, parse_line)?;
+    vec.sort_by_key(|e| e.datetime);
+    Ok(vec)
+}
+
+/// Date of the midnight a shift covers: shifts beginning in the afternoon or
+/// evening belong to the following date.
+fn shift_date(begin: NaiveDateTime) -> NaiveDate {
+    (begin + Duration::hours(12)).date()
+}
+
+#[derive(Debug)]
+pub struct Span {
+    pub date: NaiveDate,
+    pub guard_id: GuardId,
+    pub from: NaiveDateTime,
+    pub to: NaiveDateTime
+}
+
+impl Span {
+    /// Minutes of the shift's midnight hour spent asleep; sleep outside 00:00-00:59
+    /// does not count, as in the puzzle statement.
+    pub fn minutes(&self) -> impl Iterator<Item=usize> {
+        let midnight = self.date.and_hms_opt(0, 0, 0).expect("midnight is a valid time, qed.");
+        let from = (self.from - midnight).num_minutes().max(0);
+        let to = (self.to - midnight).num_minutes().min(60);
+
+        (from..to.max(from)).map(|min| min as usize)
+    }
+}
+
+#[derive(Debug)]
+pub struct Shift {
+    pub date: NaiveDate,
+    pub guard_id: GuardId,
+    pub begin: NaiveDateTime
+}
+
+/// Every shift and every sleep span of a log, in chronological order.
+pub struct Log {
+    pub shifts: Vec<Shift>,
+    pub spans: Vec<Span>
+}
+
+fn compute_log(input: &str, events: Vec<Event>) -> Result<Log, ParseError> {
+    let error = |text, what| ParseError::locate(Day4::DAY, input, Unexpected::expected(text, what));
+
+    let mut shift: Option<(GuardId, NaiveDate)> = None;
+    let mut asleep: Option<NaiveDateTime> = None;
+
+    let mut shifts = Vec::new();
+    let mut spans = Vec::new();
+
+    for Event{ datetime, action, line } in events {
+        match action {
+            Action::BeginShift(id) => {
+                if asleep.is_some() {
+                    return Err(error(line, "`wakes up` before the next shift begins"));
+                }
+
+                let date = shift_date(datetime);
+                shift = Some((id, date));
+                shifts.push(Shift{ date, guard_id: id, begin: datetime });
+            },
+
+            Action::Awake => {
+                let (guard_id, date) = shift.ok_or_else(|| error(line, "`Guard #<id> begins shift` first"))?;
+                let from = asleep.take().ok_or_else(|| error(line, "`falls asleep` before `wakes up`"))?;
+
+                spans.push(Span{ date, guard_id, from, to: datetime });
+            },
+
+            Action::Sleep => {
+                if shift.is_none() {
+                    return Err(error(line, "`Guard #<id> begins shift` first"));
+                }
+
+                if asleep.is_some() {
+                    return Err(error(line, "`wakes up` before falling asleep again"));
+                }
+
+                asleep = Some(datetime);
+            }
+        }
+    }
+
+    if asleep.is_some() {
+        return Err(ParseError::locate(Day4::DAY, input, Unexpected::new(end_of(input), ErrorKind::UnexpectedEnd)));
+    }
+
+    Ok(Log{ shifts, spans })
+}
+
+pub fn log(input: &str) -> Result<Log, ParseError> {
+    compute_log(input, sorted_events(input)?)
+}
+
+/// Sleep spans of a log, in chronological order.
+pub fn spans(input: &str) -> Result<Vec<Span>, ParseError> {
+    log(input).map(|log| log.spans)
+}
+
+/// Per shift, which minutes of its midnight hour the guard spent asleep.
+fn timeline_rows(log: &Log) -> BTreeMap<(NaiveDate, GuardId), [bool; 60]> {
+    let mut rows: BTreeMap<_, _> = log.shifts.iter().map(|s| ((s.date, s.guard_id), [false; 60])).collect();
+
+    for span in log.spans.iter() {
+        let row = rows.entry((span.date, span.guard_id)).or_insert([false; 60]);
+        for min in span.minutes() {
+            row[min] = true;
+        }
+    }
+
+    rows
+}
+
+/// The chart from the puzzle statement: one row per shift, `#` for every minute
+/// of the midnight hour spent asleep and `.` otherwise.
+pub fn timeline(log: &Log) -> String {
+    let rows = timeline_rows(log);
+    let width = rows.keys().map(|(_, id)| format!("#{}", id).len()).max().unwrap_or(0).max(2);
+
+    let mut out = format!("Date   {:<w$}  Minute\n", "ID", w = width);
+    for place in &[10, 1] {
+        let minutes: String = (0..60).map(|m| (b'0' + (m / place % 10) as u8) as char).collect();
+        out += &format!("{:>w$}{}\n", "", minutes, w = width + 9);
+    }
+
+    for ((date, id), row) in rows {
+        let minutes: String = row.iter().map(|&asleep| if asleep { '#' } else { '.' }).collect();
+        out += &format!("{}  {:<w$}  {}\n", date.format("%m-%d"), format!("#{}", id), minutes, w = width);
+    }
+
+    out
+}
+
+/// Same chart as an HTML table, asleep minutes having the `asleep` class.
+pub fn timeline_html(log: &Log) -> String {
+    let mut out = String::from("<table class=\"timeline\">\n<tr><th>Date</th><th>ID</th>");
+    for min in 0..60 {
+        out += &format!("<th>{:02}</th>", min);
+    }
+    out += "</tr>\n";
+
+    for ((date, id), row) in timeline_rows(log) {
+        out += &format!("<tr><td>{}</td><td>#{}</td>", date.format("%m-%d"), id);
+        for &asleep in row.iter() {
+            out += if asleep { "<td class=\"asleep\">#</td>" } else { "<td>.</td>" };
+        }
+        out += "</tr>\n";
+    }
+
+    out + "</table>\n"
+}
+
+impl Solution for Day4 {
+    type Input = BTreeMap<GuardId, Stats>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    const DAY: u32 = 4;
+    const INPUT: &'static str = include_str!("../inputs/4.txt");
+
+    fn parse(input: &str) -> Result<BTreeMap<GuardId, Stats>, ParseError> {
+        Ok(stats::compute(&log(input)?))
+    }
+
+    fn part_one(stats: &BTreeMap<GuardId, Stats>) -> usize {
+        stats::answer(stats, stats::Strategy::MostAsleep)
+    }
+
+    fn part_two(stats: &BTreeMap<GuardId, Stats>) -> usize {
+        stats::answer(stats, stats::Strategy::SleepiestMinute)
+    }
+
+    fn diagnostics(stats: &BTreeMap<GuardId, Stats>) -> Option<Value> {
+        Some(Value::Array(stats.iter().map(|(guard_id, s)| json!({
+            "guard_id": guard_id,
+            "shifts": s.shifts,
+            "total_asleep": s.total_asleep,
+            "mean_asleep": s.mean_asleep(),
+            "longest_nap": s.longest_nap,
+            "sleepiest_minute": s.sleepiest_minute().map(|(minute, _)| minute),
+            "freqs": &s.freqs[..]
+        })).collect()))
+    }
+}
+
+#[test]
+fn examples() {
+    let log = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+    let stats = Day4::parse(log).expect("example parses");
+
+    assert_eq!(Day4::part_one(&stats), 240);
+    assert_eq!(Day4::part_two(&stats), 4455);
+
+    assert_eq!(timeline(&self::log(log).expect("example is well formed")), "\
+Date   ID   Minute
+            000000000011111111112222222222333333333344444444445555555555
+            012345678901234567890123456789012345678901234567890123456789
+11-01  #10  .....####################.....#########################.....
+11-02  #99  ........................................##########..........
+11-03  #10  ........................#####...............................
+11-04  #99  ....................................##########..............
+11-05  #99  .............................................##########.....
+");
+
+    let log = "[1518-11-01 23:58] Guard #7 begins shift
+[1518-11-01 23:59] falls asleep
+[1518-11-02 00:02] wakes up
+";
+    let spans = spans(log).expect("log is well formed");
+    assert_eq!(spans[0].date, NaiveDate::from_ymd_opt(1518, 11, 2).expect("valid date"));
+    assert_eq!(spans[0].minutes().collect::<Vec<_>>(), vec![0, 1]);
+}
+
+#[test]
+fn awake_shift_timeline() {
+    let log = log("[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-02 00:00] Guard #11 begins shift
+[1518-11-02 00:05] falls asleep
+[1518-11-02 00:07] wakes up
+").expect("log is well formed");
+
+    assert_eq!(timeline(&log).lines().skip(3).collect::<Vec<_>>(), vec![
+        "11-01  #10  ............................................................",
+        "11-02  #11  .....##....................................................."
+    ]);
+    assert_eq!(timeline_html(&log).matches("<tr><td>").count(), 2);
+}
+
+#[test]
+fn malformed_logs() {
+    let error = |log: &str| Day4::parse(log).err().map(|e| (e.line, e.kind.to_string()));
+
+    assert_eq!(error("[1518-11-01 00:05] falls asleep"), Some((1, "expected `Guard #<id> begins shift` first".to_owned())));
+    assert_eq!(error("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:25] wakes up"),
+               Some((2, "expected `falls asleep` before `wakes up`".to_owned())));
+    assert_eq!(error("[1518-11-01 00:05] falls asleep\n[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:06] falls asleep"),
+               Some((3, "expected `wakes up` before falling asleep again".to_owned())));
+    assert_eq!(error("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-02 00:00] Guard #11 begins shift"),
+               Some((3, "expected `wakes up` before the next shift begins".to_owned())));
+    assert!(error("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep").is_some());
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    type Shift = (GuardId, Vec<usize>);
+
+    fn arb_shift() -> impl Strategy<Value=Shift> {
+        (1..6usize, prop::collection::btree_set(0..60usize, 0..10))
+            .prop_map(|(guard_id, minutes)| {
+                let mut naps: Vec<usize> = minutes.into_iter().collect();
+                naps.truncate(naps.len() / 2 * 2);
+                (guard_id, naps)
+            })
+    }
+
+    fn log_lines(shifts: &[Shift]) -> Vec<String> {
+        let first = NaiveDate::from_ymd_opt(1518, 1, 1).expect("valid date").and_hms_opt(0, 0, 0).expect("valid time");
+        let mut lines = Vec::new();
+
+        for (day, (guard_id, naps)) in shifts.iter().enumerate() {
+            let midnight = first + Duration::days(day as i64);
+            let stamp = |dt: NaiveDateTime| dt.format("[%Y-%m-%d %H:%M]").to_string();
+
+            lines.push(format!("{} Guard #{} begins shift", stamp(midnight - Duration::minutes(2)), guard_id));
+            for nap in naps.chunks(2) {
+                lines.push(format!("{} falls asleep", stamp(midnight + Duration::minutes(nap[0] as i64))));
+                lines.push(format!("{} wakes up", stamp(midnight + Duration::minutes(nap[1] as i64))));
+            }
+        }
+
+        lines
+    }
+
+    fn arb_log() -> impl Strategy<Value=(Vec<Shift>, Vec<String>)> {
+        prop::collection::vec(arb_shift(), 0..20).prop_flat_map(|shifts| {
+            let lines = log_lines(&shifts);
+            (Just(shifts), Just(lines).prop_shuffle())
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn stats_match_generated_naps((shifts, lines) in arb_log()) {
+            let stats = Day4::parse(&lines.join("\n")).expect("generated log parses");
+
+            let mut expected: BTreeMap<GuardId, Stats> = BTreeMap::new();
+            for (guard_id, naps) in shifts.iter() {
+                let entry = expected.entry(*guard_id).or_insert(Stats{ shifts: 0, total_asleep: 0, longest_nap: 0, freqs: [0; 60] });
+                entry.shifts += 1;
+                for nap in naps.chunks(2) {
+                    entry.longest_nap = entry.longest_nap.max((nap[1] - nap[0]) as u32);
+                    for min in nap[0]..nap[1] {
+                        entry.total_asleep += 1;
+                        entry.freqs[min] += 1;
+                    }
+                }
+            }
+
+            prop_assert_eq!(stats.len(), expected.len());
+            for (guard_id, e) in expected.iter() {
+                prop_assert_eq!(stats[guard_id].shifts, e.shifts);
+                prop_assert_eq!(stats[guard_id].total_asleep, e.total_asleep);
+                prop_assert_eq!(stats[guard_id].longest_nap, e.longest_nap);
+                prop_assert_eq!(&stats[guard_id].freqs[..], &e.freqs[..]);
+            }
+        }
+    }
+}
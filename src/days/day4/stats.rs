@@ -0,0 +1,125 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use super::{GuardId, Log};
+
+#[derive(Clone, Debug)]
+pub struct Stats {
+    pub shifts: usize,
+    /// Minutes asleep during 00:00-00:59, like `freqs`.
+    pub total_asleep: u32,
+    /// Whole nap, including any part outside the midnight hour.
+    pub longest_nap: u32,
+    pub freqs: [usize; 60]
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// Strategy 1: the guard with the most minutes asleep.
+    MostAsleep,
+    /// Strategy 2: the guard most frequently asleep on the same minute.
+    SleepiestMinute
+}
+
+impl Stats {
+    fn new() -> Stats {
+        Stats{ shifts: 0, total_asleep: 0, longest_nap: 0, freqs: [0; 60] }
+    }
+
+    /// Mean minutes asleep per shift.
+    pub fn mean_asleep(&self) -> f64 {
+        if self.shifts == 0 { 0.0 } else { self.total_asleep as f64 / self.shifts as f64 }
+    }
+
+    /// Minute of the hour most often spent asleep and how often, the earliest one on ties.
+    /// `None` for a guard who never slept.
+    pub fn sleepiest_minute(&self) -> Option<(usize, usize)> {
+        self.freqs.iter().cloned().enumerate()
+            .filter(|&(_, f)| f > 0)
+            .min_by_key(|&(minute, f)| (Reverse(f), minute))
+    }
+
+    fn score(&self, strategy: Strategy) -> usize {
+        match strategy {
+            Strategy::MostAsleep => self.total_asleep as usize,
+            Strategy::SleepiestMinute => self.sleepiest_minute().map_or(0, |(_, f)| f)
+        }
+    }
+}
+
+pub fn compute(log: &Log) -> BTreeMap<GuardId, Stats> {
+    let mut stats = BTreeMap::new();
+
+    for shift in log.shifts.iter() {
+        stats.entry(shift.guard_id).or_insert_with(Stats::new).shifts += 1;
+    }
+
+    for span in log.spans.iter() {
+        let entry = stats.entry(span.guard_id).or_insert_with(Stats::new);
+        entry.longest_nap = entry.longest_nap.max((span.to - span.from).num_minutes() as u32);
+        for min in span.minutes() {
+            entry.total_asleep += 1;
+            entry.freqs[min] += 1;
+        }
+    }
+
+    stats
+}
+
+/// All guards, best first under `strategy`; ties go to the lower guard ID.
+pub fn ranking(stats: &BTreeMap<GuardId, Stats>, strategy: Strategy) -> Vec<(GuardId, &Stats)> {
+    let mut ranking: Vec<_> = stats.iter().map(|(&guard_id, s)| (guard_id, s)).collect();
+    ranking.sort_by_key(|&(guard_id, s)| (Reverse(s.score(strategy)), guard_id));
+    ranking
+}
+
+/// Guard ID times sleepiest minute of the best guard, zero if nobody ever slept.
+pub fn answer(stats: &BTreeMap<GuardId, Stats>, strategy: Strategy) -> usize {
+    ranking(stats, strategy).first()
+        .and_then(|&(guard_id, s)| s.sleepiest_minute().map(|(minute, _)| guard_id * minute))
+        .unwrap_or(0)
+}
+
+#[test]
+fn tie_breaks() {
+    let log = super::log("[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:07] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:32] wakes up
+[1518-11-02 00:00] Guard #3 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:44] wakes up
+[1518-11-03 00:00] Guard #7 begins shift
+").expect("log is well formed");
+    let stats = compute(&log);
+
+    assert_eq!(stats[&10].sleepiest_minute(), Some((5, 1)));
+    assert_eq!(stats[&10].longest_nap, 2);
+    assert_eq!(stats[&7].sleepiest_minute(), None);
+    assert_eq!(stats[&7].shifts, 1);
+
+    let ids = |strategy| ranking(&stats, strategy).into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+    assert_eq!(ids(Strategy::MostAsleep), vec![3, 10, 7]);
+    assert_eq!(ids(Strategy::SleepiestMinute), vec![3, 10, 7]);
+    assert_eq!(answer(&stats, Strategy::MostAsleep), 3 * 40);
+}
+
+#[test]
+fn midnight_hour_only() {
+    let log = super::log("[1518-11-01 23:30] Guard #1 begins shift
+[1518-11-01 23:40] falls asleep
+[1518-11-02 00:05] wakes up
+[1518-11-02 23:58] Guard #1 begins shift
+[1518-11-03 00:20] falls asleep
+[1518-11-03 00:41] wakes up
+").expect("log is well formed");
+    let stats = compute(&log);
+
+    let asleep: Vec<usize> = (0..5).chain(20..41).collect();
+    assert_eq!(stats[&1].freqs.iter().enumerate().filter(|&(_, &f)| f > 0).map(|(min, _)| min).collect::<Vec<_>>(), asleep);
+    assert!(stats[&1].freqs.iter().all(|&f| f <= 1));
+    assert_eq!(stats[&1].total_asleep, 26);
+    assert_eq!(stats[&1].longest_nap, 25);
+    assert_eq!(stats[&1].sleepiest_minute(), Some((0, 1)));
+}
@@ -0,0 +1,421 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{self, Read, Write};
+use std::thread;
+
+use error::{ParseError, Unexpected};
+use Solution;
+
+pub struct Day5;
+
+pub type Seq = Vec<u8>;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Reaction {
+    Annihilate,
+    /// Both units are replaced by a new one, which may react further.
+    Rewrite(u8)
+}
+
+/// What happens when `left` ends up right next to `right`, if anything.
+pub trait Rule {
+    fn react(&self, left: u8, right: u8) -> Option<Reaction>;
+}
+
+impl<R: Rule> Rule for &R {
+    fn react(&self, left: u8, right: u8) -> Option<Reaction> {
+        (*self).react(left, right)
+    }
+}
+
+/// The puzzle rule: same letter in opposite cases annihilates. ASCII cases differ
+/// only in the 0x20 bit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Polarity;
+
+impl Rule for Polarity {
+    fn react(&self, left: u8, right: u8) -> Option<Reaction> {
+        if left ^ right == 0x20 && left.is_ascii_alphabetic() { Some(Reaction::Annihilate) } else { None }
+    }
+}
+
+/// Arbitrary reactions between ordered pairs of units.
+#[derive(Clone, Debug, Default)]
+pub struct RuleTable {
+    reactions: HashMap<(u8, u8), Reaction>
+}
+
+impl RuleTable {
+    pub fn new() -> RuleTable {
+        RuleTable::default()
+    }
+
+    /// The puzzle rule as a table.
+    pub fn polarity() -> RuleTable {
+        let mut table = RuleTable::new();
+        for lower in b'a'..=b'z' {
+            table.insert(lower, lower.to_ascii_uppercase(), Reaction::Annihilate);
+            table.insert(lower.to_ascii_uppercase(), lower, Reaction::Annihilate);
+        }
+
+        table
+    }
+
+    pub fn insert(&mut self, left: u8, right: u8, reaction: Reaction) {
+        self.reactions.insert((left, right), reaction);
+    }
+}
+
+impl Rule for RuleTable {
+    fn react(&self, left: u8, right: u8) -> Option<Reaction> {
+        self.reactions.get(&(left, right)).cloned()
+    }
+}
+
+/// Incremental reduction: the already reacted prefix is inert, so a new unit can
+/// only react with its top. Always reducing the leftmost reactive pair, this agrees
+/// with `react_naive_with` for any rule, and memory is bounded by the reacted polymer.
+#[derive(Default)]
+pub struct Reactor<R = Polarity> {
+    stack: Seq,
+    rule: R
+}
+
+impl Reactor {
+    pub fn new() -> Reactor {
+        Reactor::default()
+    }
+}
+
+impl<R: Rule> Reactor<R> {
+    pub fn with_rule(rule: R) -> Reactor<R> {
+        Reactor{ stack: Vec::new(), rule }
+    }
+
+    pub fn push(&mut self, unit: u8) {
+        if let Some(unit) = settle(&self.rule, &mut self.stack, unit, |&top| top, |_, _, _| ()) {
+            self.stack.push(unit);
+        }
+    }
+
+    pub fn extend(&mut self, units: &[u8]) {
+        for &unit in units {
+            self.push(unit);
+        }
+    }
+
+    pub fn units(&self) -> &[u8] {
+        &self.stack
+    }
+
+    pub fn into_units(self) -> Seq {
+        self.stack
+    }
+}
+
+/// Reacts `unit` with the top of `stack` until either is inert or the unit is gone,
+/// handing every popped top to `on_reaction` together with the unit it met. Returns
+/// what is left of the unit, to be pushed by the caller.
+fn settle<T, R, U, F>(rule: &R, stack: &mut Vec<T>, unit: u8, unit_of: U, mut on_reaction: F) -> Option<u8>
+    where R: Rule, U: Fn(&T) -> u8, F: FnMut(T, u8, Reaction)
+{
+    let mut unit = unit;
+
+    while let Some(reaction) = stack.last().and_then(|top| rule.react(unit_of(top), unit)) {
+        let top = stack.pop().expect("reacted with the top, qed.");
+        on_reaction(top, unit, reaction);
+
+        unit = match reaction {
+            Reaction::Annihilate => return None,
+            Reaction::Rewrite(product) => product
+        };
+    }
+
+    Some(unit)
+}
+
+pub fn react(seq: &[u8]) -> Seq {
+    react_with(Polarity, seq)
+}
+
+pub fn react_with<R: Rule>(rule: R, seq: &[u8]) -> Seq {
+    let mut reactor = Reactor{ stack: Vec::with_capacity(seq.len()), rule };
+    reactor.extend(seq);
+    reactor.into_units()
+}
+
+pub fn react_stream<T: Read>(reader: T) -> io::Result<Seq> {
+    react_stream_with(Polarity, reader)
+}
+
+/// Reacts a polymer read in chunks from `reader`, ignoring whitespace. Fails with
+/// `InvalidData` on anything other than ASCII letters.
+pub fn react_stream_with<R: Rule, T: Read>(rule: R, mut reader: T) -> io::Result<Seq> {
+    let mut reactor = Reactor::with_rule(rule);
+    let mut buf = [0; 1 << 16];
+    let mut offset = 0;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => return Ok(reactor.into_units()),
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e)
+        };
+
+        for (i, &unit) in buf[..n].iter().enumerate() {
+            if unit.is_ascii_alphabetic() {
+                reactor.push(unit);
+            } else if !unit.is_ascii_whitespace() {
+                let message = format!("byte {}: expected polymer unit (ASCII letter), found {:?}", offset + i, unit as char);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+        }
+
+        offset += n;
+    }
+}
+
+pub fn react_naive(seq: Seq) -> Seq {
+    react_naive_with(Polarity, seq)
+}
+
+/// Rescans from the start after every reaction, O(n^2); kept as a reference.
+pub fn react_naive_with<R: Rule>(rule: R, mut seq: Seq) -> Seq {
+    while let Some((i, reaction)) = (1..seq.len()).find_map(|i| rule.react(seq[i - 1], seq[i]).map(|r| (i - 1, r))) {
+        match reaction {
+            Reaction::Annihilate => { seq.drain(i..(i+2)); },
+            Reaction::Rewrite(product) => { seq.splice(i..(i+2), Some(product)); }
+        }
+    }
+
+    seq
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Step {
+    /// 1-based position in the order reactions happen.
+    pub step: usize,
+    pub left: usize,
+    pub right: usize,
+    pub units: (u8, u8),
+    pub reaction: Reaction
+}
+
+/// Original input index of every surviving unit and of both sides of every reaction.
+/// A rewrite product takes over the index of the unit whose arrival produced it.
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    pub survivors: Vec<(usize, u8)>,
+    pub steps: Vec<Step>
+}
+
+impl Trace {
+    /// One row per reaction followed by one per survivor, under the header
+    /// `kind,step,left,right,units,product`; survivors only fill `left` and `units`.
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "kind,step,left,right,units,product")?;
+
+        for s in self.steps.iter() {
+            let (kind, product) = match s.reaction {
+                Reaction::Annihilate => ("annihilate", String::new()),
+                Reaction::Rewrite(product) => ("rewrite", (product as char).to_string())
+            };
+
+            writeln!(out, "{},{},{},{},{}{},{}", kind, s.step, s.left, s.right, s.units.0 as char, s.units.1 as char, product)?;
+        }
+
+        for &(index, unit) in self.survivors.iter() {
+            writeln!(out, "survivor,,{},,{},", index, unit as char)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Same reduction as `Reactor`, remembering where every unit came from.
+pub fn trace_with<R: Rule>(rule: R, seq: &[u8]) -> Trace {
+    let mut survivors = Vec::new();
+    let mut steps: Vec<Step> = Vec::new();
+
+    for (index, &unit) in seq.iter().enumerate() {
+        let on_reaction = |(left, top), right, reaction| {
+            steps.push(Step{ step: steps.len() + 1, left, right: index, units: (top, right), reaction });
+        };
+
+        if let Some(unit) = settle(&rule, &mut survivors, unit, |&(_, top)| top, on_reaction) {
+            survivors.push((index, unit));
+        }
+    }
+
+    Trace{ survivors, steps }
+}
+
+pub fn trace(seq: &[u8]) -> Trace {
+    trace_with(Polarity, seq)
+}
+
+fn without_unit(seq: &[u8], unit: u8) -> Seq {
+    seq.iter().filter(|c| c.to_ascii_lowercase() != unit).cloned().collect()
+}
+
+/// Length of the fully reacted polymer after removing each (lowercase) unit type
+/// occurring in `seq`, one thread per unit. Removal commutes with reaction, so
+/// every thread starts from the already reacted polymer.
+pub fn removal_lengths(seq: &[u8]) -> BTreeMap<u8, usize> {
+    let units: BTreeSet<u8> = seq.iter().map(u8::to_ascii_lowercase).collect();
+    let reacted = react(seq);
+    let reacted = &reacted;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = units.into_iter()
+            .map(|unit| (unit, scope.spawn(move || react(&without_unit(reacted, unit)).len())))
+            .collect();
+
+        handles.into_iter()
+            .map(|(unit, handle)| (unit, handle.join().expect("reaction does not panic, qed.")))
+            .collect()
+    })
+}
+
+impl Solution for Day5 {
+    type Input = Seq;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    const DAY: u32 = 5;
+    const INPUT: &'static str = include_str!("inputs/5.txt");
+
+    /// Whitespace is ignored anywhere, as in `react_stream`.
+    fn parse(input: &str) -> Result<Seq, ParseError> {
+        if let Some((i, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_alphabetic() && !c.is_ascii_whitespace()) {
+            let unexpected = Unexpected::expected(&input[i..i + c.len_utf8()], "polymer unit (ASCII letter)");
+            return Err(ParseError::locate(Self::DAY, input, unexpected));
+        }
+
+        Ok(input.bytes().filter(|b| !b.is_ascii_whitespace()).collect())
+    }
+
+    fn part_one(seq: &Seq) -> usize {
+        react(seq).len()
+    }
+
+    fn part_two(seq: &Seq) -> usize {
+        removal_lengths(seq).values().cloned().min().unwrap_or(0)
+    }
+}
+
+#[test]
+fn examples() {
+    let seq = Day5::parse("dabAcCaCBAcCcaDA\n").expect("example parses");
+
+    assert_eq!(Day5::part_one(&seq), 10);
+    assert_eq!(Day5::part_two(&seq), 4);
+    assert_eq!(removal_lengths(&seq).into_iter().collect::<Vec<_>>(), vec![(b'a', 6), (b'b', 8), (b'c', 4), (b'd', 6)]);
+}
+
+#[test]
+fn trace_csv() {
+    let trace = trace(b"dabAcCaCBAcCcaDA");
+    assert_eq!(trace.survivors.iter().map(|&(index, _)| index).collect::<Vec<_>>(), vec![0, 1, 2, 7, 8, 9, 12, 13, 14, 15]);
+    assert_eq!(trace.steps.iter().map(|s| (s.step, s.left, s.right)).collect::<Vec<_>>(), vec![(1, 4, 5), (2, 3, 6), (3, 10, 11)]);
+
+    let mut csv = Vec::new();
+    trace.write_csv(&mut csv).expect("writing to a Vec succeeds");
+    assert!(String::from_utf8(csv).expect("csv is utf-8").starts_with("kind,step,left,right,units,product\nannihilate,1,4,5,cC,\n"));
+}
+
+#[test]
+fn custom_rules() {
+    let mut rules = RuleTable::new();
+    rules.insert(b'a', b'b', Reaction::Rewrite(b'c'));
+    rules.insert(b'c', b'c', Reaction::Annihilate);
+    assert_eq!(react_with(&rules, b"abcab"), b"c");
+    assert_eq!(react_with(&rules, b"ccab"), b"c");
+}
+
+#[test]
+fn streaming() {
+    let wrapped = "dabAcCaC\nBAcCcaDA\n";
+
+    assert_eq!(react_stream(wrapped.as_bytes()).expect("example streams"), b"dabCBAcaDA");
+    assert_eq!(react(&Day5::parse(wrapped).expect("wrapped example parses")), b"dabCBAcaDA");
+
+    assert!(react_stream("dabAc1".as_bytes()).is_err());
+    assert!(Day5::parse("dabAc1").is_err());
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn arb_polymer() -> impl Strategy<Value=Seq> {
+        prop::collection::vec(prop::sample::select(b"aAbBcC".to_vec()), 0..200)
+    }
+
+    fn arb_rules() -> impl Strategy<Value=RuleTable> {
+        let unit = || prop::sample::select(b"abc".to_vec());
+        let reaction = prop_oneof![Just(Reaction::Annihilate), unit().prop_map(Reaction::Rewrite)];
+
+        prop::collection::vec((unit(), unit(), reaction), 0..6).prop_map(|reactions| {
+            let mut rules = RuleTable::new();
+            for (left, right, reaction) in reactions {
+                rules.insert(left, right, reaction);
+            }
+
+            rules
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn reacted_polymer_is_inert(seq in arb_polymer()) {
+            let reacted = react(&seq);
+
+            prop_assert!(!reacted.windows(2).any(|w| Polarity.react(w[0], w[1]).is_some()));
+            prop_assert_eq!(reacted.len() % 2, seq.len() % 2);
+            prop_assert_eq!(react(&reacted), reacted);
+        }
+
+        #[test]
+        fn react_matches_naive_reference(seq in arb_polymer()) {
+            prop_assert_eq!(react(&seq), react_naive(seq.clone()));
+        }
+
+        #[test]
+        fn polarity_table_matches_rule(seq in arb_polymer()) {
+            prop_assert_eq!(react_with(RuleTable::polarity(), &seq), react(&seq));
+        }
+
+        #[test]
+        fn reactor_matches_naive_for_any_rules(rules in arb_rules(), seq in prop::collection::vec(prop::sample::select(b"abc".to_vec()), 0..50)) {
+            prop_assert_eq!(react_with(&rules, &seq), react_naive_with(&rules, seq.clone()));
+        }
+
+        #[test]
+        fn trace_accounts_for_every_unit(rules in arb_rules(), seq in prop::collection::vec(prop::sample::select(b"abc".to_vec()), 0..50)) {
+            let trace = trace_with(&rules, &seq);
+            let survivors: Seq = trace.survivors.iter().map(|&(_, unit)| unit).collect();
+
+            prop_assert_eq!(survivors, react_with(&rules, &seq));
+            prop_assert!(trace.survivors.windows(2).all(|w| w[0].0 < w[1].0));
+            prop_assert!(trace.steps.iter().all(|s| s.left < s.right && s.right < seq.len()));
+            prop_assert_eq!(trace.steps.iter().filter(|s| s.reaction == Reaction::Annihilate).count() * 2
+                            + trace.steps.iter().filter(|s| s.reaction != Reaction::Annihilate).count()
+                            + trace.survivors.len(), seq.len());
+        }
+
+        #[test]
+        fn stream_matches_slice(seq in arb_polymer()) {
+            prop_assert_eq!(react_stream(&seq[..]).expect("polymer streams"), react(&seq));
+        }
+
+        #[test]
+        fn removal_lengths_match_sequential(seq in arb_polymer()) {
+            for (unit, len) in removal_lengths(&seq) {
+                prop_assert_eq!(len, react_naive(without_unit(&seq, unit)).len());
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::FromIterator;
+
+use itertools::Itertools;
+use ndarray::{Array2, indices_of};
+use serde_json::Value;
+
+use error::{end_of, parse_lines, ErrorKind, ParseError, Unexpected};
+use Solution;
+
+const STEPS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const DIST_LIMIT: usize = 10000;
+
+pub struct Day6;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Point {
+    x: i32,
+    y: i32
+}
+
+impl Point {
+    fn adj<'a>(&'a self) -> impl Iterator<Item = Point> + 'a {
+        STEPS.iter().map(move |(dx, dy)| Point{ x: self.x + dx, y: self.y + dy })
+    }
+
+    fn dist(&self, other: &Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+#[derive(Debug)]
+struct Bounds {
+    min: i32,
+    max: i32
+}
+
+impl Bounds {
+    fn size(&self) -> usize {
+        (self.max - self.min + 1) as usize
+    }
+
+    fn rel(&self, x: i32) -> Option<usize> {
+        if self.min <= x && x <= self.max { Some((x - self.min) as usize) } else { None }
+    }
+
+    fn expand(&self, span: usize) -> Bounds {
+        Bounds{ min: self.min - span as i32, max: self.max + span as i32 }
+    }
+
+    fn range(&self) -> impl Iterator<Item=i32> + Clone {
+        self.min..(self.max+1)
+    }
+}
+
+#[derive(Debug)]
+struct Aabb {
+    x: Bounds,
+    y: Bounds
+}
+
+impl Aabb {
+    fn size(&self) -> (usize, usize) {
+        (self.x.size(), self.y.size())
+    }
+
+    fn rel(&self, p: Point) -> Option<(usize, usize)> {
+        Some((self.x.rel(p.x)?, self.y.rel(p.y)?))
+    }
+
+    fn is_boundary(&self, (x, y): (usize, usize)) -> bool {
+        x == 0 || x+1 == self.x.size() || y == 0 || y+1 == self.y.size()
+    }
+
+    fn expand(&self, span: usize) -> Aabb {
+        Aabb{ x: self.x.expand(span), y: self.y.expand(span) }
+    }
+
+    fn domain(&self) -> impl Iterator<Item=Point> {
+        iproduct!(self.x.range(), self.y.range()).map(|(x, y)| Point{ x, y })
+    }
+}
+
+fn parse_line(line: &str) -> Result<Point, Unexpected<'_>> {
+    line.split(", ")
+        .map(|s| s.parse::<i32>().map_err(|e| Unexpected::number(s, e)))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|vec| match *vec.as_slice() {
+            [x, y] => Ok(Point{ x, y }),
+            _ => Err(Unexpected::expected(line, "coordinates like `1, 6`"))
+        })
+}
+
+fn parse_points(input: &str) -> Result<Vec<Point>, ParseError> {
+    let points = parse_lines(Day6::DAY, input, parse_line)?;
+
+    if points.is_empty() {
+        return Err(ParseError::locate(Day6::DAY, input, Unexpected::new(end_of(input), ErrorKind::UnexpectedEnd)));
+    }
+
+    Ok(points)
+}
+
+fn bounds_by_key<F>(points: &[Point], f: F) -> Bounds where F: Fn(&Point) -> i32 {
+    points
+        .iter()
+        .map(f)
+        .minmax()
+        .into_option()
+        .map(|(min, max)| Bounds{ min, max })
+        .expect("more than one")
+}
+
+fn compute_aabb(points: &[Point]) -> Aabb {
+    Aabb {
+        x: bounds_by_key(points, |p| p.x),
+        y: bounds_by_key(points, |p| p.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SlotMark {
+    Empty,
+    Closest{ depth: usize, id: usize },
+    Tie
+}
+
+fn region_size(points: &[Point], dist_limit: usize) -> usize {
+    let aabb = compute_aabb(points);
+    let wide_aabb = aabb.expand(dist_limit / points.len());
+
+    wide_aabb.domain().filter(|domain_p| {
+        let overflown = points.iter()
+            .scan(0, |state, p| {
+                *state += p.dist(domain_p);
+                Some(*state)
+            })
+            .any(|total_dist| total_dist >= dist_limit as i32);
+
+        !overflown
+    }).count()
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Largest {
+    Area(usize),
+    Nowhere
+}
+
+impl From<Largest> for Value {
+    fn from(largest: Largest) -> Value {
+        match largest {
+            Largest::Area(area) => area.into(),
+            Largest::Nowhere => Value::Null
+        }
+    }
+}
+
+impl fmt::Display for Largest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Largest::Area(area) => write!(f, "{}", area),
+            Largest::Nowhere => write!(f, "no finite region")
+        }
+    }
+}
+
+impl Solution for Day6 {
+    type Input = Vec<Point>;
+    type PartOne = Largest;
+    type PartTwo = usize;
+
+    const DAY: u32 = 6;
+    const INPUT: &'static str = include_str!("inputs/6.txt");
+
+    fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+        parse_points(input)
+    }
+
+    fn part_one(points: &Vec<Point>) -> Largest {
+        let aabb = compute_aabb(points);
+
+        let mut slots = Array2::from_elem(aabb.size(), SlotMark::Empty);
+
+        for (id, &p) in points.iter().enumerate() {
+            slots[aabb.rel(p).expect("all within aabb, qed")] = SlotMark::Closest{ depth: 0, id };
+        }
+
+        let mut queue = VecDeque::from_iter(points.iter().cloned().enumerate());
+
+        while let Some((cur_id, cur_p)) = queue.pop_front() {
+            let ix = aabb.rel(cur_p).expect("should be within aabb");
+
+            let cur_depth = match slots[ix] {
+                SlotMark::Tie => { continue; },
+                SlotMark::Closest{ depth, id } if id == cur_id => depth,
+                _ => unreachable!()
+            };
+
+            for (adj_p, adj_ix) in cur_p.adj().filter_map(|p| aabb.rel(p).map(|ix| (p, ix))) {
+                match slots[adj_ix] {
+                    SlotMark::Empty => {
+                        slots[adj_ix] = SlotMark::Closest{ depth: cur_depth + 1, id: cur_id };
+                        queue.push_back((cur_id, adj_p));
+                    },
+
+                    SlotMark::Closest{ depth, id } if id != cur_id && depth == cur_depth + 1 => {
+                        slots[adj_ix] = SlotMark::Tie;
+                    },
+
+                    _ => {}
+                }
+            }
+        }
+
+        let mut boundary = vec![false; points.len()];
+        let mut counts = vec![0usize; points.len()];
+
+        for ix in indices_of(&slots) {
+            if let SlotMark::Closest{ id, .. } = slots[ix] {
+                counts[id] += 1;
+                if aabb.is_boundary(ix) {
+                    boundary[id] = true;
+                }
+            }
+        }
+
+        counts.iter().zip(boundary.iter())
+            .filter_map(|(&cnt, is_boundary)| if !is_boundary { Some(cnt) } else { None })
+            .max()
+            .map_or(Largest::Nowhere, Largest::Area)
+    }
+
+    fn part_two(points: &Vec<Point>) -> usize {
+        region_size(points, DIST_LIMIT)
+    }
+}
+
+#[test]
+fn examples() {
+    let points = Day6::parse("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n").expect("example parses");
+
+    assert_eq!(Day6::part_one(&points), Largest::Area(17));
+    assert_eq!(region_size(&points, 32), 16);
+}
+
+#[test]
+fn no_finite_region() {
+    let points = Day6::parse("1, 1\n").expect("point parses");
+    assert_eq!(Day6::part_one(&points), Largest::Nowhere);
+    assert_eq!(Largest::Nowhere.to_string(), "no finite region");
+}
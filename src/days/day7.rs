@@ -0,0 +1,235 @@
+use std::collections::{HashSet, HashMap};
+
+use regex::Regex;
+
+use error::{parse_lines, ParseError, Unexpected};
+use Solution;
+
+const WORKERS: usize = 5;
+const BASE_SECONDS: usize = 60;
+
+lazy_static! {
+    static ref LINE_RE: Regex = Regex::new(r"^Step (.) must be finished before step (.) can begin\.$")
+        .expect("regex should compile");
+}
+
+pub struct Day7;
+
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Letter(u8);
+
+impl Letter {
+    fn from_string(symbol: &str) -> Option<Letter> {
+        if symbol.len() != 1 {
+            return None;
+        }
+
+        match symbol.chars().next() {
+            Some(ch @ 'A'..='Z') => Some(Letter(ch as u8 - b'A')),
+            _ => None
+        }
+    }
+
+    fn seconds(&self, base_seconds: usize) -> usize {
+        base_seconds + self.0 as usize + 1
+    }
+
+    fn char(&self) -> char {
+        (self.0 + b'A') as char
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Edge {
+    from: Letter,
+    to: Letter
+}
+
+fn parse_letter(symbol: &str) -> Result<Letter, Unexpected<'_>> {
+    Letter::from_string(symbol).ok_or_else(|| Unexpected::expected(symbol, "step letter `A`..`Z`"))
+}
+
+fn parse_line(line: &str) -> Result<Edge, Unexpected<'_>> {
+    let captures = LINE_RE.captures(line)
+        .ok_or_else(|| Unexpected::expected(line, "`Step X must be finished before step Y can begin.`"))?;
+
+    Ok(Edge {
+        from: parse_letter(captures.get(1).expect("group participates, qed.").as_str())?,
+        to: parse_letter(captures.get(2).expect("group participates, qed.").as_str())?
+    })
+}
+
+fn get_sources(edges: &[Edge]) -> HashSet<Letter> {
+    let unique: HashSet<_> = edges.iter()
+        .flat_map(|Edge{ ref from, ref to }| vec![from, to])
+        .copied().collect();
+
+    let sources: HashSet<_> = unique.iter()
+        .filter(|&v| edges.iter().all(|Edge{ to, .. }| to != v))
+        .copied().collect();
+
+    sources
+}
+
+fn get_incoming(edges: &[Edge]) -> HashMap<Letter, usize> {
+    let mut incoming: HashMap<_, usize> = HashMap::new();
+    for Edge{ to, .. } in edges {
+        *incoming.entry(*to).or_default() += 1;
+    };
+
+    incoming
+}
+
+fn join_letters(letters: &[Letter]) -> String {
+    letters.iter().map(Letter::char).collect::<String>()
+}
+
+fn assembly_time(edges: &[Edge], workers: usize, base_seconds: usize) -> usize {
+    let sources = get_sources(edges);
+    let mut incoming = get_incoming(edges);
+
+    let mut queue = HashSet::new();
+    let mut frontier = sources;
+
+    let mut total_time: usize = 0;
+
+    loop {
+        while queue.len() < workers {
+            if let Some(cur) = frontier.iter().min().cloned() {
+                queue.insert((cur, total_time + cur.seconds(base_seconds)));
+                frontier.remove(&cur);
+            } else {
+                break;
+            }
+        }
+
+        let nearest = if let Some(nearest) = queue.iter().min_by_key(|(_, t)| t).cloned() {
+            nearest
+        } else {
+            break;
+        };
+
+        queue.remove(&nearest);
+
+        let (min_letter, nearest_time) = nearest;
+        total_time = nearest_time;
+
+        for Edge{ to, ..} in edges.iter().filter(|Edge{ from, .. }| *from == min_letter) {
+            let cnt = incoming.get_mut(to).expect("should exist by definition, qed.");
+            *cnt -= 1;
+            if *cnt == 0 {
+                frontier.insert(*to);
+            }
+        }
+    }
+
+    total_time
+}
+
+impl Solution for Day7 {
+    type Input = Vec<Edge>;
+    type PartOne = String;
+    type PartTwo = usize;
+
+    const DAY: u32 = 7;
+    const INPUT: &'static str = include_str!("inputs/7.txt");
+
+    fn parse(input: &str) -> Result<Vec<Edge>, ParseError> {
+        parse_lines(Self::DAY, input, parse_line)
+    }
+
+    fn part_one(edges: &Vec<Edge>) -> String {
+        let sources = get_sources(edges);
+        let mut incoming = get_incoming(edges);
+
+        let mut frontier = sources;
+        let mut ans = Vec::new();
+
+        while let Some(cur) = frontier.iter().min().cloned() {
+            frontier.remove(&cur);
+            ans.push(cur);
+
+            for Edge{ to, ..} in edges.iter().filter(|Edge{ from, .. }| *from == cur) {
+                let cnt = incoming.get_mut(to).expect("should exist by definition, qed.");
+                *cnt -= 1;
+                if *cnt == 0 {
+                    frontier.insert(*to);
+                }
+            }
+        }
+
+        join_letters(&ans)
+    }
+
+    fn part_two(edges: &Vec<Edge>) -> usize {
+        assembly_time(edges, WORKERS, BASE_SECONDS)
+    }
+}
+
+#[test]
+fn examples() {
+    let edges = Day7::parse("Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.
+").expect("example parses");
+
+    assert_eq!(Day7::part_one(&edges), "CABDFE");
+    assert_eq!(assembly_time(&edges, 2, 0), 15);
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn arb_dag() -> impl Strategy<Value=Vec<Edge>> {
+        let letters: Vec<u8> = (0..26).collect();
+
+        (Just(letters).prop_shuffle(), prop::collection::btree_set((0..26usize, 0..26usize), 1..60))
+            .prop_map(|(order, pairs)| pairs.into_iter()
+                .filter(|&(i, j)| i < j)
+                .map(|(i, j)| Edge{ from: Letter(order[i]), to: Letter(order[j]) })
+                .collect::<Vec<_>>())
+            .prop_filter("at least one edge", |edges| !edges.is_empty())
+    }
+
+    fn format_edge(e: &Edge) -> String {
+        format!("Step {} must be finished before step {} can begin.", e.from.char(), e.to.char())
+    }
+
+    proptest! {
+        #[test]
+        fn edges_round_trip(edges in arb_dag()) {
+            let input = edges.iter().map(format_edge).collect::<Vec<_>>().join("\n");
+            prop_assert_eq!(Day7::parse(&input).expect("formatted edges parse"), edges);
+        }
+
+        #[test]
+        fn order_is_topological(edges in arb_dag()) {
+            let order = Day7::part_one(&edges);
+            let position = |l: Letter| order.find(l.char());
+
+            for e in edges.iter() {
+                prop_assert!(position(e.from).is_some() && position(e.from) < position(e.to));
+            }
+
+            let letters: HashSet<Letter> = edges.iter().flat_map(|e| vec![e.from, e.to]).collect();
+            prop_assert_eq!(order.len(), letters.len());
+        }
+
+        #[test]
+        fn assembly_is_bounded(edges in arb_dag(), workers in 1..6usize) {
+            let letters: HashSet<Letter> = edges.iter().flat_map(|e| vec![e.from, e.to]).collect();
+            let total: usize = letters.iter().map(|l| l.seconds(0)).sum();
+
+            let time = assembly_time(&edges, workers, 0);
+            prop_assert!(time <= total);
+            prop_assert!(time * workers >= total);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use error::{end_of, ErrorKind, ParseError, Unexpected};
+use Solution;
+
+pub struct Day8;
+
+fn get_numbers<'a>(tokens: &[&'a str]) -> Result<Vec<u32>, Unexpected<'a>> {
+    tokens.iter()
+        .map(|slice| if slice.starts_with('-') {
+            Err(Unexpected::expected(slice, "non-negative number"))
+        } else {
+            slice.parse::<u32>().map_err(|e| Unexpected::number(slice, e))
+        })
+        .collect()
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct TreeNode {
+    children: Vec<TreeNode>,
+    meta: Vec<u32>
+}
+
+fn parse_tree(numbers: &[u32]) -> Option<(TreeNode, &[u32])> {
+    let num_children = *numbers.first()?;
+    let num_meta = *numbers.get(1)? as usize;
+
+    let mut children = Vec::new();
+    let mut remains = &numbers[2..];
+
+    for _child_idx in 0..num_children {
+        let (child_node, new_remains) = parse_tree(remains)?;
+        remains = new_remains;
+
+        children.push(child_node);
+    }
+
+    let meta = remains.get(..num_meta)?.to_vec();
+    Some((TreeNode{ children, meta }, &remains[num_meta..]))
+}
+
+fn sum_meta(node: &TreeNode) -> u64 {
+    node.children.iter().fold(0u64, |a, n| a + sum_meta(n)) + node.meta.iter().map(|&m| u64::from(m)).sum::<u64>()
+}
+
+fn sum_contrived(node: &TreeNode) -> u64 {
+    let TreeNode{ children, meta } = node;
+
+    if children.is_empty() {
+        meta.iter().map(|&m| u64::from(m)).sum::<u64>()
+
+    } else {
+        let mut seen = HashMap::new();
+        let mut sum = 0u64;
+
+        for &idx in meta.iter() {
+            if let Some(child_node) = (idx as usize).checked_sub(1).and_then(|i| children.get(i)) {
+                sum += *seen.entry(idx).or_insert_with(|| sum_contrived(child_node));
+            }
+        }
+
+        sum
+    }
+}
+
+impl Solution for Day8 {
+    type Input = TreeNode;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    const DAY: u32 = 8;
+    const INPUT: &'static str = include_str!("inputs/8.txt");
+
+    fn parse(input: &str) -> Result<TreeNode, ParseError> {
+        let tokens: Vec<&str> = input.split_whitespace().collect();
+        let numbers = get_numbers(&tokens).map_err(|u| ParseError::locate(Self::DAY, input, u))?;
+
+        let unexpected = match parse_tree(&numbers) {
+            Some((root, [])) => return Ok(root),
+            Some((_, remains)) => Unexpected::new(tokens[numbers.len() - remains.len()], ErrorKind::TrailingInput),
+            None => Unexpected::new(end_of(input), ErrorKind::UnexpectedEnd)
+        };
+
+        Err(ParseError::locate(Self::DAY, input, unexpected))
+    }
+
+    fn part_one(root: &TreeNode) -> u64 {
+        sum_meta(root)
+    }
+
+    fn part_two(root: &TreeNode) -> u64 {
+        sum_contrived(root)
+    }
+}
+
+#[test]
+fn examples() {
+    let root = Day8::parse("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n").expect("example parses");
+
+    assert_eq!(Day8::part_one(&root), 138);
+    assert_eq!(Day8::part_two(&root), 66);
+}
+
+#[test]
+fn negative_numbers() {
+    let e = Day8::parse("1 -1 0 0").expect_err("negative count is rejected");
+    assert_eq!((e.column, e.text.as_str()), (3, "-1"));
+
+    let e = Day8::parse("1 1 0 0 -2147483648").expect_err("negative metadata is rejected");
+    assert_eq!((e.column, e.text.as_str()), (9, "-2147483648"));
+
+    let root = Day8::parse("1 2 0 1 7 0 1").expect("tree parses");
+    assert_eq!(Day8::part_two(&root), 7);
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn arb_tree() -> impl Strategy<Value=TreeNode> {
+        let meta = || prop::collection::vec(0..20u32, 0..5);
+        let leaf = meta().prop_map(|meta| TreeNode{ children: Vec::new(), meta });
+
+        leaf.prop_recursive(4, 64, 5, move |inner| {
+            (prop::collection::vec(inner, 0..5), meta())
+                .prop_map(|(children, meta)| TreeNode{ children, meta })
+        })
+    }
+
+    fn serialize(node: &TreeNode, out: &mut Vec<String>) {
+        out.push(node.children.len().to_string());
+        out.push(node.meta.len().to_string());
+        for child in node.children.iter() {
+            serialize(child, out);
+        }
+        out.extend(node.meta.iter().map(u32::to_string));
+    }
+
+    fn all_meta(node: &TreeNode) -> Vec<u32> {
+        let mut ret = node.meta.clone();
+        ret.extend(node.children.iter().flat_map(all_meta));
+        ret
+    }
+
+    proptest! {
+        #[test]
+        fn tree_round_trip(tree in arb_tree()) {
+            let mut tokens = Vec::new();
+            serialize(&tree, &mut tokens);
+
+            let parsed = Day8::parse(&tokens.join(" ")).expect("serialized tree parses");
+            prop_assert_eq!(sum_meta(&parsed), all_meta(&tree).iter().map(|&m| u64::from(m)).sum::<u64>());
+            prop_assert_eq!(parsed, tree);
+        }
+
+        #[test]
+        fn truncated_tree_is_rejected(tree in arb_tree()) {
+            let mut tokens = Vec::new();
+            serialize(&tree, &mut tokens);
+            tokens.pop();
+
+            prop_assert!(Day8::parse(&tokens.join(" ")).is_err());
+        }
+    }
+}
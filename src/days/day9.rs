@@ -0,0 +1,125 @@
+use std::iter::{once, FromIterator};
+use std::collections::VecDeque;
+
+use regex::{Regex, Match};
+
+use error::{ParseError, Unexpected};
+use Solution;
+
+lazy_static! {
+    static ref LINE_RE: Regex = Regex::new(r"^(\d+) players; last marble is worth (\d+) points$")
+        .expect("regex should compile");
+}
+
+pub struct Day9;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Game {
+    pub players: usize,
+    pub last_marble: usize
+}
+
+fn parse_number(m: Option<Match<'_>>) -> Result<usize, Unexpected<'_>> {
+    let text = m.expect("group participates, qed.").as_str();
+    text.parse::<usize>().map_err(|e| Unexpected::number(text, e))
+}
+
+fn parse_line(line: &str) -> Result<Game, Unexpected<'_>> {
+    let captures = LINE_RE.captures(line)
+        .ok_or_else(|| Unexpected::expected(line, "`<n> players; last marble is worth <n> points`"))?;
+
+    let players = parse_number(captures.get(1))?;
+    if players == 0 {
+        return Err(Unexpected::expected(captures.get(1).expect("group participates, qed.").as_str(), "at least one player"));
+    }
+
+    Ok(Game {
+        players,
+        last_marble: parse_number(captures.get(2))?
+    })
+}
+
+struct Circle(VecDeque<usize>);
+
+impl Circle {
+    fn new(elem: usize) -> Self {
+        Circle(VecDeque::from_iter(once(elem)))
+    }
+
+    fn rotate_cw(&mut self) {
+        let front = self.0.pop_front().expect("circle nonempty");
+        self.0.push_back(front);
+    }
+
+    fn rotate_ccw(&mut self) {
+        let back = self.0.pop_back().expect("circle nonempty");
+        self.0.push_front(back);
+    }
+
+    fn insert_step(&mut self, elem: usize) {
+        self.rotate_cw();
+        self.0.push_back(elem);
+    }
+
+    fn strange_step(&mut self) -> usize {
+        (0..7).for_each(|_| self.rotate_ccw());
+        let evicted = self.0.pop_back().expect("circle nonempty");
+        self.rotate_cw();
+        evicted
+    }
+}
+
+fn highscore(n_players: usize, last_marble: usize) -> usize {
+    let mut scores = vec![0usize; n_players];
+    let mut circle = Circle::new(0);
+
+    for marble in 1..=last_marble {
+        if marble % 23 == 0 {
+            let rem = circle.strange_step();
+            scores[marble % n_players] += marble + rem;
+
+        } else {
+            circle.insert_step(marble);
+        }
+    }
+
+    scores.iter().max().cloned().expect("at least one player")
+}
+
+impl Solution for Day9 {
+    type Input = Game;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    const DAY: u32 = 9;
+    const INPUT: &'static str = include_str!("inputs/9.txt");
+
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        parse_line(input.trim()).map_err(|u| ParseError::locate(Self::DAY, input, u))
+    }
+
+    fn part_one(game: &Game) -> usize {
+        highscore(game.players, game.last_marble)
+    }
+
+    fn part_two(game: &Game) -> usize {
+        highscore(game.players, game.last_marble * 100)
+    }
+}
+
+#[test]
+fn examples() {
+    assert_eq!(highscore(10, 1618), 8317);
+    assert_eq!(highscore(13, 7999), 146373);
+    assert_eq!(highscore(17, 1104), 2764);
+    assert_eq!(highscore(21, 6111), 54718);
+    assert_eq!(highscore(30, 5807), 37305);
+
+    assert_eq!(Day9::parse("10 players; last marble is worth 1618 points\n"), Ok(Game{ players: 10, last_marble: 1618 }));
+}
+
+#[test]
+fn no_players() {
+    let e = Day9::parse("0 players; last marble is worth 100 points").expect_err("zero players is rejected");
+    assert_eq!((e.column, e.text.as_str()), (1, "0"));
+}
@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ErrorKind {
+    Expected(&'static str),
+    InvalidNumber(ParseIntError),
+    OutOfRange(&'static str),
+    UnexpectedEnd,
+    TrailingInput
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Expected(what) => write!(f, "expected {}", what),
+            ErrorKind::InvalidNumber(e) => write!(f, "invalid number ({})", e),
+            ErrorKind::OutOfRange(range) => write!(f, "number out of {} range", range),
+            ErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ErrorKind::TrailingInput => write!(f, "trailing input")
+        }
+    }
+}
+
+/// Offending slice of the input, not yet located within it.
+#[derive(Debug)]
+pub struct Unexpected<'a> {
+    pub text: &'a str,
+    pub kind: ErrorKind
+}
+
+impl<'a> Unexpected<'a> {
+    pub fn new(text: &'a str, kind: ErrorKind) -> Unexpected<'a> {
+        Unexpected{ text, kind }
+    }
+
+    pub fn expected(text: &'a str, what: &'static str) -> Unexpected<'a> {
+        Unexpected::new(text, ErrorKind::Expected(what))
+    }
+
+    pub fn number(text: &'a str, e: ParseIntError) -> Unexpected<'a> {
+        Unexpected::new(text, ErrorKind::InvalidNumber(e))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ErrorKind
+}
+
+impl ParseError {
+    /// Finds the line and column of `unexpected.text`, which must be a slice of `input`.
+    pub fn locate(day: u32, input: &str, unexpected: Unexpected) -> ParseError {
+        let offset = (unexpected.text.as_ptr() as usize).checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + unexpected.text.len() <= input.len())
+            .expect("offending text is a slice of the input, qed.");
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: unexpected.text.to_owned(),
+            kind: unexpected.kind
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {}, line {}, column {}: {}", self.day, self.line, self.column, self.kind)?;
+        if !self.text.is_empty() {
+            write!(f, ", found `{}`", self.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses every nonempty line of `input` with `f`.
+pub fn parse_lines<'a, T, F>(day: u32, input: &'a str, f: F) -> Result<Vec<T>, ParseError>
+    where F: Fn(&'a str) -> Result<T, Unexpected<'a>>
+{
+    input.lines().map(str::trim).filter(|l| !l.is_empty())
+        .map(|l| f(l).map_err(|u| ParseError::locate(day, input, u)))
+        .collect()
+}
+
+/// Location pointing just past the end of `input`.
+pub fn end_of(input: &str) -> &str {
+    &input[input.len()..]
+}
@@ -0,0 +1,27 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+
+/// Reads puzzle input from `path`, where `-` stands for stdin.
+pub fn read(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        Ok(buf)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// Input named by the first command line argument, falling back to the bundled one.
+pub fn load(bundled: &str) -> String {
+    match env::args().nth(1) {
+        Some(path) => read(&path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            process::exit(1)
+        }),
+
+        None => bundled.to_owned()
+    }
+}
@@ -0,0 +1,56 @@
+extern crate regex;
+extern crate chrono;
+extern crate ndarray;
+extern crate toml;
+#[macro_use] extern crate serde_json;
+#[macro_use] extern crate itertools;
+#[macro_use] extern crate lazy_static;
+#[cfg(test)] extern crate proptest;
+
+pub mod input;
+pub mod error;
+pub mod days;
+pub mod registry;
+pub mod answers;
+
+use std::fmt::Display;
+use std::process;
+
+use serde_json::Value;
+
+use error::ParseError;
+
+/// A day of the calendar: its input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input;
+    type PartOne: Display + Into<Value>;
+    type PartTwo: Display + Into<Value>;
+
+    const DAY: u32;
+    const INPUT: &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_one(input: &Self::Input) -> Self::PartOne;
+    fn part_two(input: &Self::Input) -> Self::PartTwo;
+
+    /// Intermediate results worth reporting alongside the answers.
+    fn diagnostics(_input: &Self::Input) -> Option<Value> {
+        None
+    }
+}
+
+/// Entry point of the per-day binaries.
+pub fn main<S: Solution>() {
+    match S::parse(&input::load(S::INPUT)) {
+        Ok(input) => print_answers::<S>(&input),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1)
+        }
+    }
+}
+
+pub fn print_answers<S: Solution>(input: &S::Input) {
+    println!("{}", S::part_one(input));
+    println!("{}", S::part_two(input));
+}
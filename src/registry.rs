@@ -0,0 +1,108 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use days::*;
+use error::ParseError;
+use Solution;
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_string(s: &str) -> Option<Part> {
+        match s {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None
+        }
+    }
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+pub struct Answer {
+    pub part: Part,
+    pub answer: String,
+    /// The typed answer, for JSON output.
+    pub value: Value,
+    pub elapsed: Duration
+}
+
+pub struct Report {
+    pub day: u32,
+    pub parse_elapsed: Duration,
+    pub answers: Vec<Answer>,
+    pub diagnostics: Option<Value>
+}
+
+pub struct Entry {
+    pub day: u32,
+    pub input: &'static str,
+    /// Solves `parts` of the given input, collecting diagnostics only when asked to.
+    pub solve: fn(&str, &[Part], bool) -> Result<Report, ParseError>
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let ret = f();
+    (ret, start.elapsed())
+}
+
+fn typed<T: fmt::Display + Into<Value>>((answer, elapsed): (T, Duration)) -> (String, Value, Duration) {
+    (answer.to_string(), answer.into(), elapsed)
+}
+
+fn solve<S: Solution>(input: &str, parts: &[Part], want_diagnostics: bool) -> Result<Report, ParseError> {
+    let (parsed, parse_elapsed) = timed(|| S::parse(input));
+    let parsed = parsed?;
+
+    let answers = parts.iter().map(|&part| {
+        let (answer, value, elapsed) = match part {
+            Part::One => typed(timed(|| S::part_one(&parsed))),
+            Part::Two => typed(timed(|| S::part_two(&parsed)))
+        };
+
+        Answer{ part, answer, value, elapsed }
+    }).collect();
+
+    let diagnostics = if want_diagnostics { S::diagnostics(&parsed) } else { None };
+    Ok(Report{ day: S::DAY, parse_elapsed, answers, diagnostics })
+}
+
+macro_rules! entry {
+    ($s:ty) => (Entry{ day: <$s as Solution>::DAY, input: <$s as Solution>::INPUT, solve: solve::<$s> })
+}
+
+pub static DAYS: [Entry; 9] = [
+    entry!(Day1),
+    entry!(Day2),
+    entry!(Day3),
+    entry!(Day4),
+    entry!(Day5),
+    entry!(Day6),
+    entry!(Day7),
+    entry!(Day8),
+    entry!(Day9)
+];
+
+pub fn find(day: u32) -> Option<&'static Entry> {
+    DAYS.iter().find(|e| e.day == day)
+}